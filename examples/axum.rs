@@ -1,26 +1,13 @@
-use axum::{response::IntoResponse, routing::get, Extension, Router};
-use demo::{DocLocale, Locale};
-use i18nify::axum::I18nifyLayer;
+use axum::{response::IntoResponse, routing::get, Router};
+use demo::DocLocale;
+use i18nify::axum::{I18nifyLayer, Lang};
 
 mod demo {
-    use i18nify::{Internationalization, I18N};
+    use i18nify::I18N;
 
-    #[derive(I18N,Clone)]
-    #[i18n(folder = "$CARGO_MANIFEST_DIR/tests/zh_locales")]
+    #[derive(I18N,Clone,Default)]
+    #[i18n(folder = "$CARGO_MANIFEST_DIR/tests/zh_locales", default = "en")]
     pub struct DocLocale;
-
-    impl Internationalization for DocLocale {
-        type Item = Locale;
-        
-        fn i(&self,lang:&str)->Self::Item {
-            match lang.to_lowercase().as_str() {
-                "en"=> Locale::En,
-                "zh-cn"=> Locale::ZhCn,
-                _=> Locale::En, 
-            }
-        }
-    }
-
 }
 
 
@@ -36,6 +23,6 @@ async fn main() {
 }
 
 // basic handler that responds with a static string
-async fn root(Extension(locale):Extension<Locale>) -> impl IntoResponse {
+async fn root(Lang(locale): Lang<DocLocale>) -> impl IntoResponse {
     locale.greeting()
 }
\ No newline at end of file
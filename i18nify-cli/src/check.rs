@@ -0,0 +1,254 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{
+    error::{Error, MissingKeysInLocale, Result},
+    locales::{self, Diagnostic, LocaleFile},
+    schema::{Config, Key, Translations},
+    validation::{
+        build_translations_from_files, validate_max_lengths, validate_placeholder_formats,
+        validate_placeholder_names, validate_tags, validate_translations,
+    },
+};
+
+/// Runs the validations of the `I18N` derive on the locale files in `folder`.
+///
+/// Unlike the derive, which stops at the first error, every key is checked on its own so all
+/// problems are reported at once. Placeholder names differing between locales, which the derive
+/// accepts, are reported as well.
+pub(crate) fn check(folder: &Path, config: &Config) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let files = locales::load(folder, config, &mut diagnostics)?;
+    if files.is_empty() {
+        if diagnostics.is_empty() {
+            diagnostics.push(Diagnostic {
+                path: folder.to_path_buf(),
+                line: None,
+                message: "No locale files found".to_string(),
+            });
+        }
+        return Ok(diagnostics);
+    }
+
+    check_files(folder, files, config, &mut diagnostics);
+    Ok(diagnostics)
+}
+
+/// Checks the keys of the parsed `files` of `folder` against each other.
+fn check_files(
+    folder: &Path,
+    files: Vec<LocaleFile>,
+    config: &Config,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let (files, translations) = build_translations(files, config, diagnostics);
+    let folder_diagnostic = |message: String| Diagnostic {
+        path: folder.to_path_buf(),
+        line: None,
+        message,
+    };
+
+    if let Err(Error::MissingKeysInLocale(missing)) = validate_translations(&translations) {
+        for MissingKeysInLocale { locale_name, keys } in missing {
+            let path = files
+                .iter()
+                .find(|file| file.locale_name == locale_name)
+                .map_or_else(|| folder.to_path_buf(), |file| file.path.clone());
+            let mut keys = keys.into_iter().collect::<Vec<_>>();
+            keys.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for key in keys {
+                let message = match defined_in(&files, &key) {
+                    Some(other) => format!(
+                        "Missing key `{}`, defined in {}",
+                        key.0,
+                        other.location(&key)
+                    ),
+                    None => format!("Missing key `{}`", key.0),
+                };
+                diagnostics.push(Diagnostic {
+                    path: path.clone(),
+                    line: None,
+                    message,
+                });
+            }
+        }
+    }
+
+    let mut translations = translations.into_iter().collect::<Vec<_>>();
+    translations.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+    for (key, translations_for_key) in translations {
+        let translations: Translations = HashMap::from([(key.clone(), translations_for_key)]);
+        let validations: [fn(&Translations) -> Result<()>; 4] = [
+            validate_placeholder_names,
+            validate_placeholder_formats,
+            validate_tags,
+            validate_max_lengths,
+        ];
+
+        for validate in validations {
+            if let Err(error) = validate(&translations) {
+                let locale_name = match &error {
+                    Error::MismatchedPlaceholders(mismatch) => Some(&mismatch.locale_name),
+                    Error::MismatchedPlaceholderFormats(mismatch) => Some(&mismatch.locale_name),
+                    Error::MismatchedTags { locale_name, .. }
+                    | Error::MaxLengthExceeded { locale_name, .. } => Some(locale_name),
+                    _ => None,
+                };
+                let file = files
+                    .iter()
+                    .find(|file| Some(&file.locale_name) == locale_name)
+                    .or_else(|| defined_in(&files, &key));
+                diagnostics.push(match file {
+                    Some(file) => file.diagnostic(&key, error.to_string()),
+                    None => folder_diagnostic(error.to_string()),
+                });
+            }
+        }
+    }
+}
+
+/// The translations of `files`, leaving out the files they cannot be built from, such as a second
+/// file of a locale, and reporting them in `diagnostics`.
+fn build_translations(
+    files: Vec<LocaleFile>,
+    config: &Config,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> (Vec<LocaleFile>, Translations) {
+    let mut built: Vec<LocaleFile> = Vec::with_capacity(files.len());
+    let mut translations: Translations = HashMap::new();
+    for file in files {
+        if let Some(other) = built.iter().find(|other| other.locale_name == file.locale_name) {
+            diagnostics.push(Diagnostic {
+                path: file.path.clone(),
+                line: None,
+                message: format!(
+                    "Locale {} is also defined in {}",
+                    file.locale_name,
+                    other.path.display()
+                ),
+            });
+            continue;
+        }
+
+        match build_translations_from_files(&[(&file.path, file.contents.clone())], config) {
+            Ok(file_translations) => {
+                for (key, translations_for_key) in file_translations {
+                    translations
+                        .entry(key)
+                        .or_default()
+                        .extend(translations_for_key);
+                }
+                built.push(file);
+            }
+            Err(error) => diagnostics.push(file.error_diagnostic(&error)),
+        }
+    }
+
+    (built, translations)
+}
+
+/// The first locale file defining `key`.
+fn defined_in<'a>(files: &'a [LocaleFile], key: &Key) -> Option<&'a LocaleFile> {
+    files.iter().find(|file| file.lines.contains_key(key))
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::schema::LocaleName;
+    #[allow(unused_imports)]
+    use std::path::PathBuf;
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_check() {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/check_locales");
+        let diagnostics = check(&folder, &Config::default())
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                let location = diagnostic.location();
+                let file = location.rsplit('/').next().unwrap().to_string();
+                (file, diagnostic.message.clone())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(diagnostics.len(), 4, "{:?}", diagnostics);
+        assert_eq!(
+            diagnostics[0],
+            (
+                "es.json:3".to_string(),
+                "Unbalanced placeholders in string, Locale: Es, String: Hola {name".to_string()
+            )
+        );
+        assert_eq!(diagnostics[1].0, "fr.json:4");
+        assert!(diagnostics[1].1.contains("expected `,` or `}`"));
+        assert_eq!(
+            diagnostics[2],
+            (
+                "de.json".to_string(),
+                format!(
+                    "Missing key `farewell`, defined in {}:3",
+                    folder.join("en.json").display()
+                )
+            )
+        );
+        assert_eq!(
+            diagnostics[3],
+            (
+                "en.json:4".to_string(),
+                "Placeholder `price` of key `total` is currency in En but number in De".to_string()
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_check_placeholder_names() {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/placeholder_locales");
+        let diagnostics = check(&folder, &Config::default()).unwrap();
+
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert!(diagnostics[0].location().ends_with("en.json:2"));
+        assert_eq!(
+            diagnostics[0].message,
+            "Placeholders differ between locales, Key: greeting, En: [\"name\"], De: [\"nmae\"]"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_check_reports_a_second_file_of_a_locale() {
+        let file = |path: &str, locale_name: &str, contents: &str| LocaleFile {
+            path: PathBuf::from(path),
+            contents: contents.to_string(),
+            locale_name: LocaleName::new(locale_name),
+            lines: HashMap::from([(Key("greeting".to_string()), 2)]),
+            names: HashMap::from([(Key("greeting".to_string()), "greeting".to_string())]),
+        };
+        let files = vec![
+            file("locales/de.json", "De", "{\n    \"greeting\": \"Hallo\"\n}"),
+            file("locales/en.json", "En", "{\n    \"greeting\": \"Hello\"\n}"),
+            file("locales/En.json", "En", "{\n    \"greeting\": \"Hi\"\n}"),
+        ];
+
+        let mut diagnostics = Vec::new();
+        check_files(Path::new("locales"), files, &Config::default(), &mut diagnostics);
+
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].location(), "locales/En.json");
+        assert_eq!(
+            diagnostics[0].message,
+            "Locale En is also defined in locales/en.json"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_check_valid_folder() {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/format_locales");
+        assert!(check(&folder, &Config::default()).unwrap().is_empty());
+    }
+}
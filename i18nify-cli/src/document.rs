@@ -0,0 +1,249 @@
+//! Edits of locale files that keep the order of their entries.
+
+use crate::error::Result;
+
+/// A locale file being edited.
+#[cfg(feature = "json")]
+pub(crate) struct Document {
+    entries: serde_json::Map<String, serde_json::Value>,
+    indent: String,
+    trailing_newline: bool,
+}
+
+/// A locale file being edited.
+#[cfg(feature = "toml")]
+pub(crate) struct Document(toml_edit::DocumentMut);
+
+#[cfg(feature = "json")]
+impl Document {
+    /// Parses `contents`, empty for a new file.
+    pub(crate) fn parse(contents: &str) -> Result<Self> {
+        let entries = if contents.trim().is_empty() {
+            serde_json::Map::new()
+        } else {
+            serde_json::from_str(contents)?
+        };
+        let indent = contents
+            .lines()
+            .find_map(|line| {
+                let trimmed = line.trim_start();
+                trimmed
+                    .starts_with('"')
+                    .then(|| &line[..line.len() - trimmed.len()])
+            })
+            .unwrap_or("    ")
+            .to_string();
+
+        Ok(Document {
+            entries,
+            indent,
+            trailing_newline: contents.is_empty() || contents.ends_with('\n'),
+        })
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) {
+        self.entries.shift_remove(name);
+    }
+
+    /// Sets the text of the entry `name` for `context`, adding the entry when missing.
+    pub(crate) fn set_text(
+        &mut self,
+        name: &str,
+        context: Option<&str>,
+        text: &str,
+    ) -> std::result::Result<(), String> {
+        use serde_json::{json, Value};
+
+        let has_context = |entry: &Value| entry.get("context").and_then(Value::as_str) == context;
+
+        let entry = self.entries.entry(name).or_insert_with(|| match context {
+            None => Value::String(String::new()),
+            Some(_) => Value::Array(Vec::new()),
+        });
+        match entry {
+            Value::String(value) if context.is_none() => *value = text.to_string(),
+            Value::Object(object) if object.get("context").and_then(Value::as_str) == context => {
+                object.insert("value".to_string(), text.into());
+            }
+            Value::Array(entries) => match entries.iter_mut().find(|entry| has_context(entry)) {
+                Some(Value::Object(object)) => {
+                    object.insert("value".to_string(), text.into());
+                }
+                _ => entries.push(json!({ "value": text, "context": context })),
+            },
+            _ => return Err(context_mismatch(name, context)),
+        }
+
+        Ok(())
+    }
+
+    /// The edited file, indented as it was.
+    pub(crate) fn render(&self) -> Result<String> {
+        use serde::Serialize;
+
+        let mut out = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(self.indent.as_bytes());
+        self.entries
+            .serialize(&mut serde_json::Serializer::with_formatter(
+                &mut out, formatter,
+            ))?;
+        if self.trailing_newline {
+            out.push(b'\n');
+        }
+
+        Ok(String::from_utf8(out).expect("serde_json writes UTF-8"))
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Document {
+    /// Parses `contents`, empty for a new file.
+    pub(crate) fn parse(contents: &str) -> Result<Self> {
+        contents
+            .parse::<toml_edit::DocumentMut>()
+            .map(Document)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error).into())
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) {
+        self.0.remove(name);
+    }
+
+    /// Sets the text of the entry `name` for `context`, adding the entry when missing.
+    pub(crate) fn set_text(
+        &mut self,
+        name: &str,
+        context: Option<&str>,
+        text: &str,
+    ) -> std::result::Result<(), String> {
+        use toml_edit::{Array, InlineTable, Item, Table, Value};
+
+        let new_entry = || {
+            let mut entry = InlineTable::new();
+            entry.insert("value", text.into());
+            if let Some(context) = context {
+                entry.insert("context", context.into());
+            }
+            entry
+        };
+        let has_context = |entry: Option<&Item>| entry.and_then(Item::as_str) == context;
+
+        let Some(item) = self.0.get_mut(name) else {
+            self.0[name] = match context {
+                None => toml_edit::value(text),
+                Some(_) => toml_edit::value(Array::from_iter([new_entry()])),
+            };
+            return Ok(());
+        };
+
+        if let Some(value) = item.as_value_mut().filter(|value| value.is_str()) {
+            if context.is_none() {
+                let decor = value.decor().clone();
+                *value = text.into();
+                *value.decor_mut() = decor;
+                return Ok(());
+            }
+        } else if let Some(entry) = item.as_table_like_mut() {
+            if has_context(entry.get("context")) {
+                entry.insert("value", toml_edit::value(text));
+                return Ok(());
+            }
+        } else if let Some(entries) = item.as_array_mut() {
+            let existing = entries.iter_mut().find_map(|entry| {
+                entry
+                    .as_inline_table_mut()
+                    .filter(|entry| entry.get("context").and_then(Value::as_str) == context)
+            });
+            match existing {
+                Some(entry) => {
+                    entry.insert("value", text.into());
+                }
+                None => entries.push(new_entry()),
+            }
+            return Ok(());
+        } else if let Some(entries) = item.as_array_of_tables_mut() {
+            let existing = entries
+                .iter_mut()
+                .find(|entry| has_context(entry.get("context")));
+            match existing {
+                Some(entry) => {
+                    entry.insert("value", toml_edit::value(text));
+                }
+                None => entries.push(Table::from_iter(new_entry())),
+            }
+            return Ok(());
+        }
+
+        Err(context_mismatch(name, context))
+    }
+
+    /// The edited file, formatted as it was.
+    pub(crate) fn render(&self) -> Result<String> {
+        Ok(self.0.to_string())
+    }
+}
+
+fn context_mismatch(name: &str, context: Option<&str>) -> String {
+    match context {
+        Some(context) => format!("`{}` has a single text, not one for `{}`", name, context),
+        None => format!("`{}` has a text per context, a context is needed", name),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_set_text() {
+        let contents = "{\n  \"save\": \"Save\",\n  \"open\": [{\"value\": \"Open\", \"context\": \"verb\"}],\n  \"title\": {\"value\": \"Title\", \"max_length\": 8}\n}\n";
+        let mut document = Document::parse(contents).unwrap();
+        document.set_text("save", None, "Gem").unwrap();
+        document.set_text("open", Some("verb"), "Åbn").unwrap();
+        document
+            .set_text("open", Some("adjective"), "Åben")
+            .unwrap();
+        document.set_text("title", None, "Titel").unwrap();
+        document.set_text("new", None, "Ny").unwrap();
+        assert!(document.set_text("save", Some("verb"), "Gem").is_err());
+
+        assert_eq!(
+            document.render().unwrap(),
+            r#"{
+  "save": "Gem",
+  "open": [
+    {
+      "value": "Åbn",
+      "context": "verb"
+    },
+    {
+      "value": "Åben",
+      "context": "adjective"
+    }
+  ],
+  "title": {
+    "value": "Titel",
+    "max_length": 8
+  },
+  "new": "Ny"
+}
+"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_set_text() {
+        let contents = "# Settings\nsave = \"Save\" # button\nopen = [{ value = \"Open\", context = \"verb\" }]\n";
+        let mut document = Document::parse(contents).unwrap();
+        document.set_text("save", None, "Gem").unwrap();
+        document.set_text("open", Some("verb"), "Åbn").unwrap();
+        document.set_text("new", None, "Ny").unwrap();
+
+        assert_eq!(
+            document.render().unwrap(),
+            "# Settings\nsave = \"Gem\" # button\nopen = [{ value = \"Åbn\", context = \"verb\" }]\nnew = \"Ny\"\n"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! A header row, then a row per text:
+//!
+//! ```text
+//! key,context,description,placeholders,en,de
+//! greeting,,Shown on the home page,name,Hello {name},Hallo {name}
+//! ```
+
+use super::{invalid_data, Row, Table};
+use crate::error::Result;
+
+const COLUMNS: [&str; 4] = ["key", "context", "description", "placeholders"];
+
+pub(super) fn write(table: &Table) -> Result<String> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    let header = COLUMNS
+        .iter()
+        .map(|column| column.to_string())
+        .chain(table.tags.iter().cloned());
+    writer.write_record(header).map_err(std::io::Error::from)?;
+
+    for row in &table.rows {
+        let record = [
+            Some(row.name.as_str()),
+            row.context.as_deref(),
+            row.description.as_deref(),
+            Some(row.placeholders.as_str()),
+        ]
+        .into_iter()
+        .chain(row.texts.iter().map(Option::as_deref))
+        .map(Option::unwrap_or_default);
+        writer.write_record(record).map_err(std::io::Error::from)?;
+    }
+
+    let out = writer.into_inner().map_err(|error| error.into_error())?;
+    Ok(String::from_utf8(out).expect("the CSV of UTF-8 strings"))
+}
+
+/// Reads the columns by name, in any order, every column besides `key`, `context`,
+/// `description` and `placeholders` being the texts of a locale.
+pub(super) fn read(contents: &str) -> Result<Table> {
+    let mut reader = ::csv::Reader::from_reader(contents.as_bytes());
+    let header = reader
+        .headers()
+        .map_err(std::io::Error::from)?
+        .iter()
+        .map(|column| column.trim().to_string())
+        .collect::<Vec<_>>();
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let key = column("key").ok_or_else(|| invalid_data("No `key` column"))?;
+    let (context, description, placeholders) = (
+        column("context"),
+        column("description"),
+        column("placeholders"),
+    );
+    let locales = (0..header.len())
+        .filter(|index| !COLUMNS.contains(&header[*index].as_str()))
+        .collect::<Vec<_>>();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(std::io::Error::from)?;
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .filter(|cell| !cell.is_empty())
+                .map(str::to_string)
+        };
+        rows.push(Row {
+            name: record.get(key).unwrap_or_default().to_string(),
+            context: cell(context),
+            description: cell(description),
+            placeholders: cell(placeholders).unwrap_or_default(),
+            texts: locales.iter().map(|index| cell(Some(*index))).collect(),
+            line: record.position().map(|position| position.line() as usize),
+        });
+    }
+
+    Ok(Table {
+        tags: locales.iter().map(|index| header[*index].clone()).collect(),
+        rows,
+    })
+}
@@ -0,0 +1,440 @@
+//! Exchanging translations with translators as CSV or XLIFF files.
+
+use std::{
+    collections::HashMap,
+    iter,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    document::Document,
+    error::{Error, Result},
+    locales::{self, line_of, same_tag, Diagnostic, LocaleFile},
+    placeholder_parsing::{find_placeholders, find_tags},
+    schema::{Config, Format as PlaceholderFormat, LocaleName, Metadata},
+    utils::{language_tag, parse_translations_file, uppercase_first_letter},
+};
+
+mod csv;
+mod xliff;
+
+/// The format of an exchanged file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Format {
+    Csv,
+    /// XLIFF 1.2, with a `<file>` per translated locale
+    Xliff,
+}
+
+impl Format {
+    /// The format of `path` by its extension, `.csv`, `.xlf` or `.xliff`.
+    pub(crate) fn of(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "xlf" | "xliff" => Some(Format::Xliff),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn write(self, table: &Table) -> Result<String> {
+        match self {
+            Format::Csv => csv::write(table),
+            Format::Xliff => Ok(xliff::write(table)),
+        }
+    }
+
+    pub(crate) fn read(self, contents: &str) -> Result<Table> {
+        match self {
+            Format::Csv => csv::read(contents),
+            Format::Xliff => xliff::read(contents),
+        }
+    }
+}
+
+/// The texts of every locale, a row per text of the source locale.
+#[derive(Debug)]
+pub(crate) struct Table {
+    /// The language tags of the locales, the source first when exported
+    pub(crate) tags: Vec<String>,
+    pub(crate) rows: Vec<Row>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Row {
+    /// The entry in the locale files, e.g. `menu.save`
+    pub(crate) name: String,
+    pub(crate) context: Option<String>,
+    pub(crate) description: Option<String>,
+    /// The placeholders of the source text, e.g. `count (number), name`
+    pub(crate) placeholders: String,
+    /// The text of each locale of `Table::tags`, `None` when untranslated
+    pub(crate) texts: Vec<Option<String>>,
+    /// The line of the row in an imported file
+    pub(crate) line: Option<usize>,
+}
+
+impl Row {
+    /// `name`, or `name#context` for a text with a context.
+    pub(crate) fn id(&self) -> String {
+        match &self.context {
+            Some(context) => format!("{}#{}", self.name, context),
+            None => self.name.clone(),
+        }
+    }
+
+    /// An empty row for the text identified by `id`, see [`Row::id`].
+    pub(crate) fn from_id(id: &str) -> Row {
+        let (name, context) = match id.split_once('#') {
+            Some((name, context)) => (name, Some(context.to_string())),
+            None => (id, None),
+        };
+        Row {
+            name: name.to_string(),
+            context,
+            description: None,
+            placeholders: String::new(),
+            texts: Vec::new(),
+            line: None,
+        }
+    }
+}
+
+/// The text of an entry for a context, with its description.
+type Text = ((String, Option<String>), (String, Metadata));
+
+/// The texts of `file` as written, placeholder formats included, in the order of the file.
+fn texts(file: &LocaleFile) -> Result<Vec<Text>> {
+    let mut texts = parse_translations_file(&file.contents)?
+        .into_iter()
+        .flat_map(|(name, entry)| {
+            entry
+                .into_values()
+                .into_iter()
+                .map(move |(value, metadata)| {
+                    ((name.clone(), metadata.context.clone()), (value, metadata))
+                })
+        })
+        .collect::<Vec<_>>();
+    texts.sort_by_cached_key(|((name, _), _)| (line_of(&file.contents, name), name.clone()));
+    Ok(texts)
+}
+
+/// The text of each entry and context of `file`.
+fn texts_by_id(file: &LocaleFile) -> Result<HashMap<(String, Option<String>), String>> {
+    Ok(texts(file)?
+        .into_iter()
+        .map(|(id, (value, _))| (id, value))
+        .collect())
+}
+
+/// The file of the source locale, tagged `tag`.
+fn source<'a>(files: &'a [LocaleFile], tag: &str) -> Result<&'a LocaleFile> {
+    locales::find(files, tag)
+        .ok_or_else(|| invalid_data(format!("No locale file for the source `{}`", tag)))
+}
+
+/// The texts of `files`, translated from the locale tagged `source`.
+pub(crate) fn export(files: &[LocaleFile], source: &str, config: &Config) -> Result<Table> {
+    let source = self::source(files, source)?;
+    let targets = files
+        .iter()
+        .filter(|file| file.locale_name != source.locale_name)
+        .collect::<Vec<_>>();
+    let target_texts = targets
+        .iter()
+        .map(|file| texts_by_id(file))
+        .collect::<Result<Vec<_>>>()?;
+
+    let rows = texts(source)?
+        .into_iter()
+        .map(|(id, (value, metadata))| {
+            let placeholders =
+                find_placeholders(&value, &config.open, &config.close, &source.locale_name)?;
+            let texts = iter::once(Some(value))
+                .chain(target_texts.iter().map(|texts| texts.get(&id).cloned()))
+                .collect();
+            let (name, context) = id;
+            Ok(Row {
+                name,
+                context,
+                description: metadata.description,
+                placeholders: describe_placeholders(&placeholders),
+                texts,
+                line: None,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Table {
+        tags: iter::once(source)
+            .chain(targets)
+            .map(|file| language_tag(&file.locale_name))
+            .collect(),
+        rows,
+    })
+}
+
+/// The locale files of `folder` updated with the texts of `table`, read from `path`.
+///
+/// The source locale is only a reference and is left as it is. A text whose placeholders or
+/// markup tags differ from the source text is reported in `diagnostics`. Locales without a file
+/// get a new one.
+pub(crate) fn import(
+    folder: &Path,
+    files: &[LocaleFile],
+    table: &Table,
+    path: &Path,
+    source: &str,
+    config: &Config,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Vec<(PathBuf, String)>> {
+    let source = self::source(files, source)?;
+    let source_texts = texts_by_id(source)?;
+    let extension = source.path.extension().unwrap_or_default();
+
+    let mut updates = Vec::new();
+    for (column, tag) in table.tags.iter().enumerate() {
+        if same_tag(tag, &language_tag(&source.locale_name)) {
+            continue;
+        }
+
+        let (file_path, contents, locale_name, current_texts) = match locales::find(files, tag) {
+            Some(file) => (
+                file.path.clone(),
+                file.contents.as_str(),
+                file.locale_name.clone(),
+                texts_by_id(file)?,
+            ),
+            None => {
+                let stem = tag.replace('-', "_");
+                let locale_name = LocaleName(uppercase_first_letter(&stem));
+                let file_path = folder.join(stem).with_extension(extension);
+                (file_path, "", locale_name, HashMap::new())
+            }
+        };
+
+        let mut document = Document::parse(contents)?;
+        let mut changed = false;
+        for row in &table.rows {
+            let Some(text) = row.texts.get(column).and_then(Option::as_deref) else {
+                continue;
+            };
+            let id = (row.name.clone(), row.context.clone());
+            if text.is_empty() || current_texts.get(&id).map(String::as_str) == Some(text) {
+                continue;
+            }
+
+            let result = match source_texts.get(&id) {
+                Some(source_text) => compatible(text, source_text, config, &locale_name)
+                    .and_then(|()| document.set_text(&row.name, row.context.as_deref(), text)),
+                None => Err(format!("No text in {} to translate", source.path.display())),
+            };
+            match result {
+                Ok(()) => changed = true,
+                Err(message) => diagnostics.push(Diagnostic {
+                    path: path.to_path_buf(),
+                    line: row.line,
+                    message: format!("`{}` in {}: {}", row.id(), tag, message),
+                }),
+            }
+        }
+
+        if changed {
+            updates.push((file_path, document.render()?));
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Whether `text` can translate `source`, using the same placeholders, of the same kinds, and
+/// the same markup tags.
+fn compatible(
+    text: &str,
+    source: &str,
+    config: &Config,
+    locale_name: &LocaleName,
+) -> std::result::Result<(), String> {
+    let placeholders = |text: &str| {
+        find_placeholders(text, &config.open, &config.close, locale_name)
+            .map(|placeholders| describe_placeholders(&placeholders))
+            .map_err(|error| error.to_string())
+    };
+    let (found, expected) = (placeholders(text)?, placeholders(source)?);
+    if found != expected {
+        return Err(format!(
+            "Placeholders {} differ from {} in the source",
+            quoted(&found),
+            quoted(&expected)
+        ));
+    }
+
+    let tags = |text: &str| {
+        find_tags(text, &config.open, &config.close, locale_name)
+            .map(|tags| {
+                let mut tags = tags.into_iter().collect::<Vec<_>>();
+                tags.sort();
+                tags.join(", ")
+            })
+            .map_err(|error| error.to_string())
+    };
+    let (found, expected) = (tags(text)?, tags(source)?);
+    if found != expected {
+        return Err(format!(
+            "Markup tags {} differ from {} in the source",
+            quoted(&found),
+            quoted(&expected)
+        ));
+    }
+
+    Ok(())
+}
+
+/// `count (number), name`, sorted by name, text placeholders without their kind.
+fn describe_placeholders(placeholders: &HashMap<String, PlaceholderFormat>) -> String {
+    let mut placeholders = placeholders
+        .iter()
+        .map(|(name, format)| {
+            let name = name.trim_end_matches('_');
+            match format {
+                PlaceholderFormat::Text => name.to_string(),
+                format => format!("{} ({})", name, format.kind()),
+            }
+        })
+        .collect::<Vec<_>>();
+    placeholders.sort();
+    placeholders.join(", ")
+}
+
+fn quoted(list: &str) -> String {
+    if list.is_empty() {
+        "(none)".to_string()
+    } else {
+        format!("`{}`", list)
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error).into()
+}
+
+/// The line of the byte at `position` in `contents`.
+fn line_at(contents: &str, position: usize) -> usize {
+    contents[..position.min(contents.len())]
+        .matches('\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(feature = "json")]
+    fn load(folder: &str) -> (PathBuf, Vec<LocaleFile>) {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../tests")
+            .join(folder);
+        let mut diagnostics = Vec::new();
+        let files = crate::locales::load(&folder, &Config::default(), &mut diagnostics).unwrap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        (folder, files)
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_export() {
+        let (_, files) = load("doc_locales");
+        let table = export(&files, "en", &Config::default()).unwrap();
+
+        assert_eq!(
+            Format::Csv.write(&table).unwrap(),
+            "key,context,description,placeholders,en,da\n\
+             hello_world,,,,\"Hello, World!\",\"Hej, Verden!\"\n\
+             greeting,,,name,Hello {name},Hej {name}\n"
+        );
+        for format in [Format::Csv, Format::Xliff] {
+            let read = format.read(&format.write(&table).unwrap()).unwrap();
+            assert_eq!(read.tags, ["en", "da"]);
+            assert_eq!(read.rows[1].id(), "greeting");
+            assert_eq!(read.rows[1].texts[1].as_deref(), Some("Hej {name}"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_import() {
+        let (folder, files) = load("doc_locales");
+        let path = Path::new("da.csv");
+        let table = Format::Csv
+            .read(
+                "key,da,sv\n\
+                 hello_world,\"Hej, Verden!\",\"Hej, Världen!\"\n\
+                 greeting,Hej {navn},Hej {name}\n\
+                 farewell,Farvel,\n",
+            )
+            .unwrap();
+
+        let mut diagnostics = Vec::new();
+        let updates = import(
+            &folder,
+            &files,
+            &table,
+            path,
+            "en",
+            &Config::default(),
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics[0],
+            (
+                Some(3),
+                "`greeting` in da: Placeholders `navn` differ from `name` in the source"
+            )
+        );
+        assert!(diagnostics[1]
+            .1
+            .starts_with("`farewell` in da: No text in "));
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, folder.join("sv.json"));
+        assert_eq!(
+            updates[0].1,
+            "{\n    \"hello_world\": \"Hej, Världen!\",\n    \"greeting\": \"Hej {name}\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_compatible() {
+        let locale = LocaleName::new("Da");
+        let config = Config::default();
+        let source = "<b>{count, number}</b> new messages for {name}";
+
+        assert!(compatible(
+            "{name}: <b>{count, number}</b> nye",
+            source,
+            &config,
+            &locale
+        )
+        .is_ok());
+        assert_eq!(
+            compatible("<b>{count}</b> nye til {name}", source, &config, &locale),
+            Err(
+                "Placeholders `count, name` differ from `count (number), name` in the source"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            compatible("{count, number} nye til {name}", source, &config, &locale),
+            Err("Markup tags (none) differ from `b` in the source".to_string())
+        );
+    }
+}
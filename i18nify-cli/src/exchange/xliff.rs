@@ -0,0 +1,156 @@
+//! XLIFF 1.2, a `<file>` per translated locale:
+//!
+//! ```xml
+//! <file original="de" source-language="en" target-language="de" datatype="plaintext">
+//!   <body>
+//!     <trans-unit id="greeting">
+//!       <source>Hello {name}</source>
+//!       <target>Hallo {name}</target>
+//!       <note>Shown on the home page</note>
+//!       <note from="placeholders">name</note>
+//!     </trans-unit>
+//!   </body>
+//! </file>
+//! ```
+
+use quick_xml::{
+    escape::escape,
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{collections::HashMap, fmt::Write};
+
+use super::{invalid_data, line_at, Row, Table};
+use crate::error::Result;
+
+pub(super) fn write(table: &Table) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n",
+    );
+    let Some(source) = table.tags.first() else {
+        out.push_str("</xliff>\n");
+        return out;
+    };
+
+    for (column, target) in table.tags.iter().enumerate().skip(1) {
+        let _ = writeln!(
+            out,
+            "  <file original=\"{}\" source-language=\"{}\" target-language=\"{}\" datatype=\"plaintext\">\n    <body>",
+            escape(target.as_str()),
+            escape(source.as_str()),
+            escape(target.as_str()),
+        );
+        for row in &table.rows {
+            let text = |column: usize| row.texts.get(column).and_then(Option::as_deref);
+            let _ = writeln!(out, "      <trans-unit id=\"{}\">", escape(row.id()));
+            let _ = writeln!(
+                out,
+                "        <source>{}</source>",
+                escape(text(0).unwrap_or_default())
+            );
+            if let Some(target) = text(column) {
+                let _ = writeln!(out, "        <target>{}</target>", escape(target));
+            }
+            if let Some(description) = &row.description {
+                let _ = writeln!(out, "        <note>{}</note>", escape(description.as_str()));
+            }
+            if !row.placeholders.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "        <note from=\"placeholders\">{}</note>",
+                    escape(row.placeholders.as_str())
+                );
+            }
+            out.push_str("      </trans-unit>\n");
+        }
+        out.push_str("    </body>\n  </file>\n");
+    }
+    out.push_str("</xliff>\n");
+
+    out
+}
+
+/// Reads the `<target>` of each `<trans-unit>`, the `<source>` being the source file's.
+pub(super) fn read(contents: &str) -> Result<Table> {
+    let mut reader = Reader::from_str(contents);
+    let mut tags = Vec::<String>::new();
+    let mut rows = Vec::<Row>::new();
+    let mut row_of_id = HashMap::new();
+
+    let mut column = None;
+    let mut row = None;
+    let mut target = None::<String>;
+    loop {
+        let position = reader.buffer_position() as usize;
+        match reader.read_event().map_err(invalid_data)? {
+            Event::Start(element) if element.local_name().as_ref() == b"file" => {
+                let source = attribute(&element, "source-language")?
+                    .ok_or_else(|| invalid_data("A <file> without a source-language"))?;
+                let target_language = attribute(&element, "target-language")?
+                    .ok_or_else(|| invalid_data("A <file> without a target-language"))?;
+                for tag in [&source, &target_language] {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                }
+                column = tags.iter().position(|tag| *tag == target_language);
+            }
+            Event::Start(element) if element.local_name().as_ref() == b"trans-unit" => {
+                let id = attribute(&element, "id")?
+                    .ok_or_else(|| invalid_data("A <trans-unit> without an id"))?;
+                row = Some(*row_of_id.entry(id.clone()).or_insert_with(|| {
+                    rows.push(Row {
+                        line: Some(line_at(contents, position)),
+                        ..Row::from_id(&id)
+                    });
+                    rows.len() - 1
+                }));
+            }
+            Event::Start(element) if element.local_name().as_ref() == b"target" => {
+                target = Some(String::new());
+            }
+            Event::Text(text) => {
+                if let Some(target) = &mut target {
+                    target.push_str(&text.unescape().map_err(invalid_data)?);
+                }
+            }
+            Event::CData(text) => {
+                if let Some(target) = &mut target {
+                    target.push_str(&String::from_utf8_lossy(&text));
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"target" => {
+                let (Some(row), Some(column), Some(target)) = (row, column, target.take()) else {
+                    return Err(invalid_data("A <target> outside of a <trans-unit>"));
+                };
+                let texts = &mut rows[row].texts;
+                if texts.len() <= column {
+                    texts.resize(column + 1, None);
+                }
+                texts[column] = Some(target);
+            }
+            Event::End(element) if element.local_name().as_ref() == b"trans-unit" => row = None,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    for row in &mut rows {
+        row.texts.resize(tags.len(), None);
+    }
+    Ok(Table { tags, rows })
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    element
+        .try_get_attribute(name)
+        .map_err(invalid_data)?
+        .map(|attribute| {
+            attribute
+                .unescape_value()
+                .map(|value| value.into_owned())
+                .map_err(invalid_data)
+        })
+        .transpose()
+}
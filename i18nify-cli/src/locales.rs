@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+    schema::{Config, Key, LocaleName},
+    utils::{
+        find_locale_files, language_tag, locale_name_from_translations_file_path,
+        parse_translations_file,
+    },
+    validation::{build_keys_from_json, key_collision},
+};
+
+/// A problem in a locale file.
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub(crate) path: PathBuf,
+    pub(crate) line: Option<usize>,
+    pub(crate) message: String,
+}
+
+impl Diagnostic {
+    /// `path:line`, or the path alone when the line is unknown.
+    pub(crate) fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path.display(), line),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: error: {}", self.location(), self.message)
+    }
+}
+
+/// A locale file whose translations all parsed.
+pub(crate) struct LocaleFile {
+    pub(crate) path: PathBuf,
+    pub(crate) contents: String,
+    pub(crate) locale_name: LocaleName,
+    /// The line of each key, named as the generated method, e.g. `menu_save` for `menu.save`
+    pub(crate) lines: HashMap<Key, usize>,
+    /// The entry in the file defining each key, e.g. `menu.save`
+    pub(crate) names: HashMap<Key, String>,
+}
+
+impl LocaleFile {
+    /// Where `key` is defined.
+    pub(crate) fn diagnostic(&self, key: &Key, message: String) -> Diagnostic {
+        Diagnostic {
+            path: self.path.clone(),
+            line: self.lines.get(key).copied(),
+            message,
+        }
+    }
+
+    /// Where `error` of this file is, as far as it names a key or comes from parsing.
+    pub(crate) fn error_diagnostic(&self, error: &Error) -> Diagnostic {
+        let line = match error {
+            Error::DuplicateKey { key, .. } => self.lines.get(&Key(key.clone())).copied(),
+            Error::ContextKeyCollision(collision) => line_of(&self.contents, &collision.key),
+            _ => parse_error_line(error, &self.contents),
+        };
+        Diagnostic {
+            path: self.path.clone(),
+            line,
+            message: error.to_string(),
+        }
+    }
+
+        /// `path:line` of `key`.
+    pub(crate) fn location(&self, key: &Key) -> String {
+        self.diagnostic(key, String::new()).location()
+    }
+}
+
+/// Parses the locale files in `folder`, sorted by path.
+///
+/// Files with errors are reported in `diagnostics` and left out.
+pub(crate) fn load(
+    folder: &Path,
+    config: &Config,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Vec<LocaleFile>> {
+    let mut paths = find_locale_files(folder)?;
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let contents = std::fs::read_to_string(&path)?;
+        let locale_name = locale_name_from_translations_file_path(&path)?;
+        let map = match parse_translations_file(&contents) {
+            Ok(map) => map,
+            Err(error) => {
+                diagnostics.push(Diagnostic {
+                    line: parse_error_line(&error, &contents),
+                    path,
+                    message: error.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let mut entries = map.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut names = HashMap::new();
+        let mut contexts = HashMap::new();
+        let mut lines = HashMap::new();
+        let mut valid = true;
+        for (name, entry) in entries {
+            let line = line_of(&contents, &name);
+            let keys = match build_keys_from_json(
+                HashMap::from([(name.clone(), entry)]),
+                config,
+                &locale_name,
+            ) {
+                Ok(keys) => keys,
+                Err(error) => {
+                    diagnostics.push(Diagnostic {
+                        path: path.clone(),
+                        line,
+                        message: error.to_string(),
+                    });
+                    valid = false;
+                    continue;
+                }
+            };
+
+            for key in keys {
+                let context = key.metadata.context.clone();
+                if let Some((other_name, other_context)) =
+                    contexts.insert(key.key.clone(), (name.clone(), context.clone()))
+                {
+                    let error = key_collision(
+                        &locale_name,
+                        &key.key,
+                        (&name, context.as_deref()),
+                        (&other_name, other_context.as_deref()),
+                    );
+                    diagnostics.push(Diagnostic {
+                        path: path.clone(),
+                        line,
+                        message: error.to_string(),
+                    });
+                    valid = false;
+                    continue;
+                }
+                names.insert(key.key.clone(), name.clone());
+                if let Some(line) = line {
+                    lines.insert(key.key, line);
+                }
+            }
+        }
+
+        if valid {
+            files.push(LocaleFile {
+                path,
+                contents,
+                locale_name,
+                lines,
+                names,
+            });
+        }
+    }
+
+    Ok(files)
+}
+
+/// The file of the locale tagged `tag`, e.g. `zh_CN.json` for `zh-cn`.
+pub(crate) fn find<'a>(files: &'a [LocaleFile], tag: &str) -> Option<&'a LocaleFile> {
+    files
+        .iter()
+        .find(|file| same_tag(&language_tag(&file.locale_name), tag))
+}
+
+pub(crate) fn same_tag(a: &str, b: &str) -> bool {
+    a.replace('_', "-").eq_ignore_ascii_case(&b.replace('_', "-"))
+}
+
+#[cfg_attr(not(feature = "toml"), allow(unused_variables))]
+fn parse_error_line(error: &Error, contents: &str) -> Option<usize> {
+    match error {
+        #[cfg(feature = "json")]
+        Error::JsonParsing(error) => Some(error.line()),
+        #[cfg(feature = "toml")]
+        Error::TomlParsing(error) => error
+            .span()
+            .map(|span| contents[..span.start].matches('\n').count() + 1),
+        _ => None,
+    }
+}
+
+/// The first line defining `key`, as `"key": ...` in JSON or `key = ...` in TOML.
+pub(crate) fn line_of(contents: &str, key: &str) -> Option<usize> {
+    let quoted = format!("\"{}\"", key);
+    let is_assigned = |rest: &str| rest.trim_start().starts_with([':', '=']);
+
+    contents
+        .lines()
+        .position(|line| {
+            line.match_indices(&quoted)
+                .any(|(index, _)| is_assigned(&line[index + quoted.len()..]))
+                || line.trim_start().strip_prefix(key).is_some_and(is_assigned)
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_line_of() {
+        let json = "{\n    \"title\": \"greeting\",\n    \"greeting\": \"Hello {name}\"\n}";
+        assert_eq!(line_of(json, "greeting"), Some(3));
+        assert_eq!(line_of(json, "missing"), None);
+
+        let toml = "hello_world = \"Hello\"\ngreeting = \"Hello {name}\"";
+        assert_eq!(line_of(toml, "greeting"), Some(2));
+    }
+}
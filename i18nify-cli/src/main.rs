@@ -0,0 +1,363 @@
+//! `cargo i18nify`, checks and maintains the locale files of a project without compiling it.
+//!
+//! ```text
+//! cargo i18nify check --folder locales
+//! cargo i18nify unused --folder locales --src src --fix
+//! cargo i18nify export --folder locales --source en --output translations.xlf
+//! cargo i18nify import translations.xlf --folder locales --source en
+//! cargo i18nify completeness --folder locales --source en --json
+//! ```
+
+use clap::{Args, Parser, Subcommand};
+use std::{path::PathBuf, process::ExitCode};
+
+mod check;
+mod document;
+mod exchange;
+mod locales;
+mod report;
+mod unused;
+
+#[cfg(not(any(feature = "json", feature = "toml")))]
+compile_error!("enable the `json` or the `toml` feature");
+#[cfg(all(feature = "json", feature = "toml"))]
+compile_error!(
+    "the `json` and `toml` features are mutually exclusive, build with `--no-default-features --features toml` for TOML locale files"
+);
+
+use i18nify_core::{completeness, error, placeholder_parsing, schema, utils, validation};
+
+use error::Error;
+use exchange::Format;
+use locales::LocaleFile;
+use schema::Config;
+
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    #[command(name = "i18nify", version, about)]
+    I18nify {
+        #[command(subcommand)]
+        command: Command,
+    },
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Reports parse errors, missing keys and mismatched placeholders, exiting with 1 if any
+    Check(Folder),
+    /// Lists the keys no Rust source refers to, exiting with 1 if any
+    Unused(Unused),
+    /// Writes the texts of every locale to a CSV or XLIFF file for translators
+    Export(Export),
+    /// Updates the locale files from an edited CSV or XLIFF file, exiting with 1 without writing
+    /// anything if a text changes the placeholders or markup tags of its source text
+    Import(Import),
+    /// Reports the missing, fuzzy and untranslated keys of each locale
+    Completeness(Completeness),
+}
+
+/// The options of `#[i18n(...)]` that affect parsing.
+#[derive(Args)]
+struct Folder {
+    /// The folder with the locale files
+    #[arg(long)]
+    folder: PathBuf,
+    /// The opening delimiter of placeholders
+    #[arg(long, default_value = "{")]
+    start: String,
+    /// The closing delimiter of placeholders
+    #[arg(long, default_value = "}")]
+    end: String,
+}
+
+#[derive(Args)]
+struct Unused {
+    #[command(flatten)]
+    folder: Folder,
+    /// A Rust file or a folder searched for `.rs` files, can be repeated
+    #[arg(long, default_value = "src")]
+    src: Vec<PathBuf>,
+    /// Removes the unused keys from the locale files
+    #[arg(long)]
+    fix: bool,
+}
+
+#[derive(Args)]
+struct Export {
+    #[command(flatten)]
+    folder: Folder,
+    /// The language tag of the locale translated from
+    #[arg(long, default_value = "en")]
+    source: String,
+    /// The format written, by default from the extension of the output, or CSV
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    /// The file written, by default the standard output
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct Import {
+    /// The CSV or XLIFF file read
+    file: PathBuf,
+    #[command(flatten)]
+    folder: Folder,
+    /// The language tag of the locale translated from, left as it is
+    #[arg(long, default_value = "en")]
+    source: String,
+    /// The format read, by default from the extension of the file
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+#[derive(Args)]
+struct Completeness {
+    #[command(flatten)]
+    folder: Folder,
+    /// The language tag of the locale translated from
+    #[arg(long, default_value = "en")]
+    source: String,
+    /// Prints JSON, for dashboards
+    #[arg(long)]
+    json: bool,
+}
+
+impl Folder {
+    fn config(&self) -> Config {
+        Config {
+            open: self.start.clone(),
+            close: self.end.clone(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let Cargo::I18nify { command } = Cargo::parse();
+
+    match command {
+        Command::Check(folder) => check(&folder),
+        Command::Unused(unused) => self::unused(&unused),
+        Command::Export(export) => self::export(&export),
+        Command::Import(import) => self::import(&import),
+        Command::Completeness(args) => completeness(&args),
+    }
+}
+
+fn check(folder: &Folder) -> ExitCode {
+    let diagnostics = match check::check(&folder.folder, &folder.config()) {
+        Ok(diagnostics) => diagnostics,
+        Err(error) => return fail(folder, error),
+    };
+    for diagnostic in &diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+
+    if diagnostics.is_empty() {
+        println!("{}: ok", folder.folder.display());
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "error: {} problem(s) in {}",
+            diagnostics.len(),
+            folder.folder.display()
+        );
+        ExitCode::FAILURE
+    }
+}
+
+fn unused(args: &Unused) -> ExitCode {
+    let folder = &args.folder;
+    let mut diagnostics = Vec::new();
+    let files = match locales::load(&folder.folder, &folder.config(), &mut diagnostics) {
+        Ok(files) => files,
+        Err(error) => return fail(folder, error),
+    };
+    let references = match unused::References::collect(&args.src, &mut diagnostics) {
+        Ok(references) => references,
+        Err(error) => return fail(folder, error),
+    };
+    if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+        eprintln!("error: fix the errors above to find the unused keys");
+        return ExitCode::FAILURE;
+    }
+
+    let unused = unused::unused_keys(&files, &references);
+    if unused.is_empty() {
+        println!("{}: no unused keys", folder.folder.display());
+        return ExitCode::SUCCESS;
+    }
+
+    for file in &files {
+        for key in unused.iter().filter(|key| file.names.contains_key(key)) {
+            let diagnostic = file.diagnostic(key, format!("Unused key `{}`", key.0));
+            if args.fix {
+                println!("{}: removed `{}`", diagnostic.location(), key.0);
+            } else {
+                eprintln!("{}", diagnostic);
+            }
+        }
+    }
+    if !args.fix {
+        eprintln!(
+            "error: {} unused key(s) in {}, remove them with --fix",
+            unused.len(),
+            folder.folder.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    for file in &files {
+        let result = unused::remove_unused(file, &unused).and_then(|contents| match contents {
+            Some(contents) => std::fs::write(&file.path, contents).map_err(Error::from),
+            None => Ok(()),
+        });
+        if let Err(error) = result {
+            eprintln!("error: {}: {}", file.path.display(), error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn export(args: &Export) -> ExitCode {
+    let folder = &args.folder;
+    let files = match load(folder) {
+        Ok(files) => files,
+        Err(code) => return code,
+    };
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(Format::of))
+        .unwrap_or(Format::Csv);
+
+    let result = exchange::export(&files, &args.source, &folder.config())
+        .and_then(|table| format.write(&table));
+    let out = match result {
+        Ok(out) => out,
+        Err(error) => return fail(folder, error),
+    };
+    match &args.output {
+        Some(output) => {
+            if let Err(error) = std::fs::write(output, out) {
+                eprintln!("error: {}: {}", output.display(), error);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", out),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn import(args: &Import) -> ExitCode {
+    let folder = &args.folder;
+    let Some(format) = args.format.or_else(|| Format::of(&args.file)) else {
+        eprintln!(
+            "error: {}: unknown format, pass --format csv or --format xliff",
+            args.file.display()
+        );
+        return ExitCode::FAILURE;
+    };
+    let table = match std::fs::read_to_string(&args.file)
+        .map_err(Error::from)
+        .and_then(|contents| format.read(&contents))
+    {
+        Ok(table) => table,
+        Err(error) => {
+            eprintln!("error: {}: {}", args.file.display(), error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let files = match load(folder) {
+        Ok(files) => files,
+        Err(code) => return code,
+    };
+
+    let mut diagnostics = Vec::new();
+    let updates = match exchange::import(
+        &folder.folder,
+        &files,
+        &table,
+        &args.file,
+        &args.source,
+        &folder.config(),
+        &mut diagnostics,
+    ) {
+        Ok(updates) => updates,
+        Err(error) => return fail(folder, error),
+    };
+    if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+        eprintln!(
+            "error: {} rejected text(s) in {}, no locale file written",
+            diagnostics.len(),
+            args.file.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    if updates.is_empty() {
+        println!("{}: no changes", folder.folder.display());
+    }
+    for (path, contents) in updates {
+        if let Err(error) = std::fs::write(&path, contents) {
+            eprintln!("error: {}: {}", path.display(), error);
+            return ExitCode::FAILURE;
+        }
+        println!("{}: updated", path.display());
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn completeness(args: &Completeness) -> ExitCode {
+    let folder = &args.folder;
+    let files = match load(folder) {
+        Ok(files) => files,
+        Err(code) => return code,
+    };
+    let report = match report::report(&files, &args.source, &folder.config()) {
+        Ok(report) => report,
+        Err(error) => return fail(folder, error),
+    };
+
+    if args.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(error) => return fail(folder, std::io::Error::from(error).into()),
+        }
+    } else {
+        print!("{}", report);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// The locale files of `folder`, reporting their errors and failing if any.
+fn load(folder: &Folder) -> Result<Vec<LocaleFile>, ExitCode> {
+    let mut diagnostics = Vec::new();
+    let files = locales::load(&folder.folder, &folder.config(), &mut diagnostics)
+        .map_err(|error| fail(folder, error))?;
+    if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic);
+        }
+        eprintln!("error: fix the errors above first");
+        return Err(ExitCode::FAILURE);
+    }
+
+    Ok(files)
+}
+
+fn fail(folder: &Folder, error: Error) -> ExitCode {
+    eprintln!("error: {}: {}", folder.folder.display(), error);
+    ExitCode::FAILURE
+}
@@ -0,0 +1,152 @@
+//! How much of each locale of a folder is translated.
+
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+use crate::{
+    completeness::completeness,
+    error::{Error, Result},
+    locales::{self, LocaleFile},
+    schema::{Config, LocaleName},
+    utils::language_tag,
+    validation::build_translations_from_files,
+};
+
+/// The completeness of every locale, serialized for dashboards:
+///
+/// ```json
+/// {
+///   "source": "en",
+///   "total": 4,
+///   "locales": [
+///     { "locale": "de", "translated": 2, "percent": 50.0, "missing": 0, "fuzzy": 1, "identical": 1 }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+pub(crate) struct Report {
+    /// The language tag of the locale translated from
+    pub(crate) source: String,
+    /// The keys of all the locales
+    pub(crate) total: usize,
+    pub(crate) locales: Vec<LocaleReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct LocaleReport {
+    pub(crate) locale: String,
+    pub(crate) translated: usize,
+    /// Rounded to one decimal
+    pub(crate) percent: f64,
+    pub(crate) missing: usize,
+    pub(crate) fuzzy: usize,
+    pub(crate) identical: usize,
+}
+
+/// The completeness of `files`, compared with the locale tagged `source`.
+pub(crate) fn report(files: &[LocaleFile], source: &str, config: &Config) -> Result<Report> {
+    let source_file = locales::find(files, source).ok_or_else(|| {
+        Error::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No locale file for the source `{}`", source),
+        ))
+    })?;
+
+    let paths_and_contents = files
+        .iter()
+        .map(|file| (&file.path, file.contents.clone()))
+        .collect::<Vec<_>>();
+    let translations = build_translations_from_files(&paths_and_contents, config)?;
+    let locale_names = files
+        .iter()
+        .map(|file| file.locale_name.clone())
+        .collect::<Vec<LocaleName>>();
+
+    let locales = completeness(&translations, &locale_names, Some(&source_file.locale_name))
+        .into_iter()
+        .zip(&locale_names)
+        .map(|(completeness, locale_name)| {
+            let percent = match completeness.total {
+                0 => 100.0,
+                total => completeness.translated() as f64 * 100.0 / total as f64,
+            };
+            LocaleReport {
+                locale: language_tag(locale_name),
+                translated: completeness.translated(),
+                percent: (percent * 10.0).round() / 10.0,
+                missing: completeness.missing,
+                fuzzy: completeness.fuzzy,
+                identical: completeness.identical,
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        source: language_tag(&source_file.locale_name),
+        total: translations.len(),
+        locales,
+    })
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} keys, compared with {}", self.total, self.source)?;
+
+        let width = self
+            .locales
+            .iter()
+            .map(|locale| locale.locale.len())
+            .chain([6])
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:<width$}  {:>10}  {:>7}  {:>5}  {:>9}",
+            "locale", "translated", "missing", "fuzzy", "identical"
+        )?;
+        for locale in &self.locales {
+            writeln!(
+                f,
+                "{:<width$}  {:>9.1}%  {:>7}  {:>5}  {:>9}",
+                locale.locale, locale.percent, locale.missing, locale.fuzzy, locale.identical
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_report() {
+        let folder =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/completeness_locales");
+        let mut diagnostics = Vec::new();
+        let files = locales::load(&folder, &Config::default(), &mut diagnostics).unwrap();
+        let report = report(&files, "en", &Config::default()).unwrap();
+
+        assert_eq!(
+            report.to_string(),
+            "4 keys, compared with en\n\
+             locale  translated  missing  fuzzy  identical\n\
+             de           50.0%        0      1          1\n\
+             en          100.0%        0      0          0\n"
+        );
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["locales"][0],
+            serde_json::json!({
+                "locale": "de",
+                "translated": 2,
+                "percent": 50.0,
+                "missing": 0,
+                "fuzzy": 1,
+                "identical": 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use proc_macro2::{TokenStream, TokenTree};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+use syn::{
+    visit::{self, Visit},
+    ExprMethodCall, ExprPath, LitStr, Macro, MetaList,
+};
+
+use crate::{
+    document::Document,
+    error::Result,
+    locales::{Diagnostic, LocaleFile},
+    schema::Key,
+};
+
+/// Names the Rust sources refer to.
+///
+/// Over-approximated, a key is only reported when nothing could be calling it.
+#[derive(Debug, Default)]
+pub(crate) struct References {
+    /// Methods called and items named by path, e.g. `greeting` in `locale.greeting()` or
+    /// `Locale::greeting`
+    idents: HashSet<String>,
+    /// String literals, e.g. in `#[localized(key = "user_not_found")]`
+    strings: HashSet<String>,
+}
+
+impl References {
+    /// Collects the references of the `.rs` files in `sources`, files or folders.
+    pub(crate) fn collect(sources: &[PathBuf], diagnostics: &mut Vec<Diagnostic>) -> Result<Self> {
+        let mut paths = Vec::new();
+        for source in sources {
+            rust_files(source, &mut paths)?;
+        }
+
+        let mut references = References::default();
+        for path in paths {
+            let contents = std::fs::read_to_string(&path)?;
+            if let Err(error) = references.parse(&contents) {
+                diagnostics.push(Diagnostic {
+                    path,
+                    line: None,
+                    message: error.to_string(),
+                });
+            }
+        }
+
+        Ok(references)
+    }
+
+    fn parse(&mut self, source: &str) -> syn::Result<()> {
+        let file = syn::parse_file(source)?;
+        self.visit_file(&file);
+        Ok(())
+    }
+
+    /// Whether `key`, defined by the entry `name`, or one of its `_html`, `_rich` and `_with`
+    /// variants is referenced.
+    pub(crate) fn uses(&self, key: &Key, name: &str) -> bool {
+        ["", "_html", "_rich", "_with"]
+            .iter()
+            .any(|suffix| self.idents.contains(&format!("{}{}", key.0, suffix)))
+            || self.strings.contains(&key.0)
+            || self.strings.contains(name)
+    }
+
+    /// Macro arguments and attributes are left unparsed by `syn`, so their method calls and paths
+    /// are found by the `.` or `::` before an identifier.
+    fn tokens(&mut self, tokens: TokenStream) {
+        let mut after_separator = false;
+        for tree in tokens {
+            match tree {
+                TokenTree::Group(group) => {
+                    self.tokens(group.stream());
+                    after_separator = false;
+                }
+                TokenTree::Punct(punct) => after_separator = matches!(punct.as_char(), '.' | ':'),
+                TokenTree::Ident(ident) => {
+                    if after_separator {
+                        self.idents.insert(ident.to_string());
+                    }
+                    after_separator = false;
+                }
+                TokenTree::Literal(literal) => {
+                    if let Ok(lit) = syn::parse_str::<LitStr>(&literal.to_string()) {
+                        self.strings.insert(lit.value());
+                    }
+                    after_separator = false;
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for References {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        self.idents.insert(call.method.to_string());
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_path(&mut self, expr: &'ast ExprPath) {
+        // A single segment is a local variable or a function in scope, not a generated method
+        if expr.path.segments.len() > 1 {
+            if let Some(segment) = expr.path.segments.last() {
+                self.idents.insert(segment.ident.to_string());
+            }
+        }
+        visit::visit_expr_path(self, expr);
+    }
+
+    fn visit_lit_str(&mut self, lit: &'ast LitStr) {
+        self.strings.insert(lit.value());
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        self.tokens(mac.tokens.clone());
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_meta_list(&mut self, list: &'ast MetaList) {
+        self.tokens(list.tokens.clone());
+        visit::visit_meta_list(self, list);
+    }
+}
+
+fn rust_files(path: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_dir() {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for entry in entries {
+            rust_files(&entry, paths)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "rs") {
+        paths.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+/// The keys of `files` no source refers to, sorted.
+pub(crate) fn unused_keys(files: &[LocaleFile], references: &References) -> Vec<Key> {
+    let mut keys = files
+        .iter()
+        .flat_map(|file| &file.names)
+        .filter(|(key, name)| !references.uses(key, name))
+        .map(|(key, _)| key.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    keys.sort_by(|a, b| a.0.cmp(&b.0));
+    keys
+}
+
+/// The contents of `file` without the entries defining only `unused` keys.
+///
+/// Entries with a context still in use are kept whole.
+pub(crate) fn remove_unused(file: &LocaleFile, unused: &[Key]) -> Result<Option<String>> {
+    let mut used_names = HashSet::new();
+    let mut unused_names = HashSet::new();
+    for (key, name) in &file.names {
+        if unused.contains(key) {
+            unused_names.insert(name.as_str());
+        } else {
+            used_names.insert(name.as_str());
+        }
+    }
+    let names = unused_names
+        .difference(&used_names)
+        .copied()
+        .collect::<HashSet<_>>();
+    if names.is_empty() {
+        return Ok(None);
+    }
+
+    without_entries(&file.contents, &names).map(Some)
+}
+
+fn without_entries(contents: &str, names: &HashSet<&str>) -> Result<String> {
+    let mut document = Document::parse(contents)?;
+    for name in names {
+        document.remove(name);
+    }
+
+    document.render()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_references() {
+        let mut references = References::default();
+        references
+            .parse(
+                r#"
+                #[derive(Debug, LocalizedError)]
+                #[localized(locale = Locale)]
+                enum ApiError {
+                    #[localized(key = "user_not_found", status = 404)]
+                    UserNotFound,
+                }
+
+                fn page(locale: Locale, title: &str) -> String {
+                    let names = ["a", "b"].map(Locale::greeting);
+                    let body = locale.terms_rich(|tag, text| text);
+                    format!("{} {}", title, locale.farewell())
+                }
+                "#,
+            )
+            .unwrap();
+
+        let uses = |key: &str| references.uses(&Key(key.to_string()), key);
+        assert!(uses("user_not_found"));
+        assert!(uses("greeting"));
+        assert!(uses("terms"));
+        assert!(uses("farewell"));
+        assert!(!uses("title"));
+        assert!(!uses("names"));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_without_entries() {
+        let contents = "{\n  \"b\": \"B\",\n  \"a\": {\"value\": \"A\", \"max_length\": 3},\n  \"c\": \"C\"\n}\n";
+        assert_eq!(
+            without_entries(contents, &HashSet::from(["a"])).unwrap(),
+            "{\n  \"b\": \"B\",\n  \"c\": \"C\"\n}\n"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! How much of each locale is translated.
+
+use crate::schema::{LocaleName, Translations};
+
+/// The keys of a locale, each counted once: missing, else fuzzy, else identical to the source
+/// text, else translated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Completeness {
+    /// The keys of all the locales
+    pub total: usize,
+    pub missing: usize,
+    /// Marked `"fuzzy": true`, to be reviewed
+    pub fuzzy: usize,
+    /// The same text as in the source locale
+    pub identical: usize,
+}
+
+impl Completeness {
+    // Used by `cargo i18nify completeness`
+    pub fn translated(&self) -> usize {
+        self.total - self.missing - self.fuzzy - self.identical
+    }
+}
+
+/// The completeness of each of `locales`, compared with the texts of `source`.
+///
+/// The source locale has no identical texts, nor has any locale without a source.
+pub fn completeness(
+    translations: &Translations,
+    locales: &[LocaleName],
+    source: Option<&LocaleName>,
+) -> Vec<Completeness> {
+    locales
+        .iter()
+        .map(|locale_name| {
+            let mut completeness = Completeness {
+                total: translations.len(),
+                ..Completeness::default()
+            };
+            for translations_for_key in translations.values() {
+                let Some((translation, _, metadata)) = translations_for_key.get(locale_name) else {
+                    completeness.missing += 1;
+                    continue;
+                };
+                let source_translation = source
+                    .filter(|source| *source != locale_name)
+                    .and_then(|source| translations_for_key.get(source))
+                    .map(|(translation, _, _)| &translation.0);
+
+                if metadata.fuzzy {
+                    completeness.fuzzy += 1;
+                } else if source_translation == Some(&translation.0) {
+                    completeness.identical += 1;
+                }
+            }
+            completeness
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::{Key, Metadata, Placeholders, Translation};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_completeness() {
+        let fuzzy = Metadata {
+            fuzzy: true,
+            ..Metadata::default()
+        };
+        let mut translations: Translations = HashMap::new();
+        for (key, texts) in [
+            (
+                "greeting",
+                vec![("En", "Hello", false), ("De", "Hallo", false)],
+            ),
+            ("save", vec![("En", "Save", false), ("De", "Save", false)]),
+            ("open", vec![("En", "Open", false), ("De", "Öffnen", true)]),
+            ("close", vec![("En", "Close", true)]),
+        ] {
+            let texts = texts
+                .into_iter()
+                .map(|(locale, text, is_fuzzy)| {
+                    let metadata = if is_fuzzy {
+                        fuzzy.clone()
+                    } else {
+                        Metadata::default()
+                    };
+                    (
+                        LocaleName::new(locale),
+                        (
+                            Translation(text.to_string()),
+                            Placeholders(HashMap::new()),
+                            metadata,
+                        ),
+                    )
+                })
+                .collect();
+            translations.insert(Key(key.to_string()), texts);
+        }
+
+        let en = LocaleName::new("En");
+        let locales = [en.clone(), LocaleName::new("De")];
+        let [en_completeness, de_completeness] =
+            completeness(&translations, &locales, Some(&en))[..]
+        else {
+            panic!("a completeness per locale");
+        };
+        assert_eq!(
+            en_completeness,
+            Completeness {
+                total: 4,
+                missing: 0,
+                fuzzy: 1,
+                identical: 0,
+            }
+        );
+        assert_eq!(
+            de_completeness,
+            Completeness {
+                total: 4,
+                missing: 1,
+                fuzzy: 1,
+                identical: 1,
+            }
+        );
+        assert_eq!(de_completeness.translated(), 1);
+    }
+}
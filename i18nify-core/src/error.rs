@@ -0,0 +1,160 @@
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::schema::{Key, LocaleName};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[cfg(feature = "json")]
+    #[error("{0}")]
+    JsonParsing(#[from] serde_json::error::Error),
+    #[cfg(feature = "toml")]
+    #[error("{0}")]
+    TomlParsing(#[from] toml::de::Error),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    // #[error("Missing environment variable `{name}`,`{inner_error}`")]
+    // MissingEnvVar {
+    //     name: String,
+    //     inner_error: std::env::VarError,
+    // },
+    // #[error("Missing folder path")]
+    // MissingFolderPath,
+    #[error("The locales directory should not contain other folders")]
+    DirectoryInLocalesFolder,
+    #[error("Failed to get file stem of locale file")]
+    NoFileStem,
+    #[error("File name contained invalid UTF-8")]
+    InvalidUtf8InFileName,
+    #[error("Unbalanced placeholders in string, Locale: {locale_name}, String: {string}")]
+    UnbalancedPlaceholders {
+        locale_name: LocaleName,
+        string: String,
+    },
+    #[error("Invalid placeholder `{placeholder}`, {reason}, Locale: {locale_name}")]
+    InvalidPlaceholder {
+        locale_name: LocaleName,
+        placeholder: String,
+        reason: String,
+    },
+    #[error("{0}")]
+    MismatchedPlaceholderFormats(Box<MismatchedPlaceholderFormats>),
+    #[error("Missing keys in locale: {0:?}")]
+    MissingKeysInLocale(Vec<MissingKeysInLocale>),
+    #[error(
+        "Markup tags differ between locales, Key: {key}, {locale_name}: {tags:?}, {other_locale_name}: {other_tags:?}"
+    )]
+    MismatchedTags {
+        key: String,
+        locale_name: LocaleName,
+        tags: Vec<String>,
+        other_locale_name: LocaleName,
+        other_tags: Vec<String>,
+    },
+    #[error("{0}")]
+    MismatchedPlaceholders(Box<MismatchedPlaceholders>),
+    #[error("Duplicate key, Locale: {locale_name}, Key: {key}")]
+    DuplicateKey { locale_name: LocaleName, key: String },
+    #[error("{0}")]
+    ContextKeyCollision(Box<ContextKeyCollision>),
+    #[error(
+        "Translation exceeds max length of {max_length} characters, Locale: {locale_name}, Key: {key}, Length: {length}"
+    )]
+    MaxLengthExceeded {
+        locale_name: LocaleName,
+        key: String,
+        max_length: usize,
+        length: usize,
+    },
+    #[error("Unsupported file format")]
+    UnsupportedFormat,
+}
+
+#[derive(Debug)]
+pub struct MissingKeysInLocale {
+    pub locale_name: LocaleName,
+    pub keys: HashSet<Key>,
+}
+
+impl Display for MissingKeysInLocale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key_lists: Vec<String> = self.keys.iter().map(|f| f.0.to_string()).collect();
+        let key_lists = key_lists.join(",");
+
+        write!(f, "[{}],{}", key_lists, self.locale_name)
+    }
+}
+
+
+#[derive(Debug)]
+pub struct MismatchedPlaceholderFormats {
+    pub key: String,
+    pub placeholder: String,
+    pub locale_name: LocaleName,
+    pub kind: &'static str,
+    pub other_locale_name: LocaleName,
+    pub other_kind: &'static str,
+}
+
+impl Display for MismatchedPlaceholderFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Placeholder `{}` of key `{}` is {} in {} but {} in {}",
+            self.placeholder,
+            self.key,
+            self.kind,
+            self.locale_name,
+            self.other_kind,
+            self.other_locale_name
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct MismatchedPlaceholders {
+    pub key: String,
+    pub locale_name: LocaleName,
+    pub placeholders: Vec<String>,
+    pub other_locale_name: LocaleName,
+    pub other_placeholders: Vec<String>,
+}
+
+impl Display for MismatchedPlaceholders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Placeholders differ between locales, Key: {}, {}: {:?}, {}: {:?}",
+            self.key,
+            self.locale_name,
+            self.placeholders,
+            self.other_locale_name,
+            self.other_placeholders
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct ContextKeyCollision {
+    pub locale_name: LocaleName,
+    pub key: String,
+    pub context: String,
+    pub other_key: String,
+    pub other_context: Option<String>,
+}
+
+impl Display for ContextKeyCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Key `{}` with context `{}` collides with the key `{}`",
+            self.key, self.context, self.other_key
+        )?;
+        if let Some(other_context) = &self.other_context {
+            write!(f, " with context `{}`", other_context)?;
+        }
+        write!(f, ", Locale: {}", self.locale_name)
+    }
+}
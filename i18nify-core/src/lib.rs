@@ -0,0 +1,16 @@
+//! Parsing and validation of the locale files of [i18nify](https://docs.rs/i18nify).
+//!
+//! Shared by the `I18N` derive of `i18nify-macro` and by `cargo-i18nify`, so both read the
+//! locale files the same way. The `json` and `toml` features select the format of the files.
+
+#[cfg(all(feature = "json", feature = "toml"))]
+compile_error!(
+    "the `json` and `toml` features are mutually exclusive, disable the default features to use `toml`"
+);
+
+pub mod completeness;
+pub mod error;
+pub mod placeholder_parsing;
+pub mod schema;
+pub mod utils;
+pub mod validation;
@@ -0,0 +1,721 @@
+use crate::{
+    error::{Error, Result},
+    schema::{
+        DateTimeFields, DateTimeLength, DateTimeOptions, Format, ListStyle, LocaleName,
+        NumberOptions, NumberStyle,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+pub fn find_placeholders(
+    s: &str,
+    start: &str,
+    end: &str,
+    locale_name: &LocaleName,
+) -> Result<HashMap<String, Format>> {
+    let tokens = tokenize(s, start, end, locale_name)?;
+
+    let mut acc = HashMap::new();
+
+    let mut inside_placeholder = false;
+    let mut current_placeholder = String::new();
+
+    for token in tokens {
+        if token.is_start() {
+            inside_placeholder = true;
+        } else if token.is_end() {
+            inside_placeholder = false;
+
+            let (mut name, format) = parse_placeholder(&current_placeholder, locale_name)?;
+            // This is necessary to allow placeholder to be Rust keywords
+            name.push('_');
+
+            match acc.get(&name) {
+                Some(existing) if *existing != format => {
+                    return Err(Error::InvalidPlaceholder {
+                        locale_name: locale_name.clone(),
+                        placeholder: current_placeholder,
+                        reason: "used with different formats in the same string".to_string(),
+                    });
+                }
+                _ => {
+                    acc.insert(name, format);
+                }
+            }
+            current_placeholder = String::new();
+        } else if inside_placeholder {
+            current_placeholder.push_str(token.token())
+        }
+    }
+
+    Ok(acc)
+}
+
+/// Rewrites the placeholders into the `{name}` form expected by `format!`, dropping their
+/// format and the custom delimiters.
+pub fn normalize_placeholders(
+    s: &str,
+    start: &str,
+    end: &str,
+    locale_name: &LocaleName,
+) -> Result<String> {
+    let tokens = tokenize(s, start, end, locale_name)?;
+
+    let mut out = String::with_capacity(s.len());
+    let mut current_placeholder = String::new();
+    let mut inside_placeholder = false;
+
+    for token in tokens {
+        match token {
+            Token::PlaceholderStart => inside_placeholder = true,
+            Token::PlaceholderEnd => {
+                inside_placeholder = false;
+                let (name, _) = parse_placeholder(&current_placeholder, locale_name)?;
+                out.push('{');
+                out.push_str(&name);
+                out.push('}');
+                current_placeholder.clear();
+            }
+            Token::TagStart(name) => {
+                out.push('<');
+                out.push_str(name);
+                out.push('>');
+            }
+            Token::TagEnd(name) => {
+                out.push_str("</");
+                out.push_str(name);
+                out.push('>');
+            }
+            Token::Char(c) if inside_placeholder => current_placeholder.push_str(c),
+            Token::Char(c) => out.push_str(c),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Splits `amount, number, percent` into the name and its format.
+fn parse_placeholder(placeholder: &str, locale_name: &LocaleName) -> Result<(String, Format)> {
+    let invalid = |reason: &str| Error::InvalidPlaceholder {
+        locale_name: locale_name.clone(),
+        placeholder: placeholder.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let mut parts = placeholder.split(',').map(str::trim);
+    let name = parts.next().unwrap_or_default().to_string();
+    if name.is_empty() {
+        return Err(invalid("missing name"));
+    }
+
+    let format = match parts.next() {
+        None => Format::Text,
+        Some("number") => {
+            let mut options = NumberOptions::default();
+            for option in parts {
+                match option.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                    None if option == "integer" => options.style = NumberStyle::Integer,
+                    None if option == "percent" => options.style = NumberStyle::Percent,
+                    None if option == "compact" => options.style = NumberStyle::Compact,
+                    Some(("min_fraction", digits)) => {
+                        options.min_fraction_digits =
+                            Some(digits.parse().map_err(|_| invalid("expected a number of digits"))?)
+                    }
+                    Some(("max_fraction", digits)) => {
+                        options.max_fraction_digits =
+                            Some(digits.parse().map_err(|_| invalid("expected a number of digits"))?)
+                    }
+                    _ => return Err(invalid(&format!("unknown number option `{}`", option))),
+                }
+            }
+            Format::Number(options)
+        }
+        Some(kind @ ("date" | "time" | "datetime")) => {
+            let fields = match kind {
+                "date" => DateTimeFields::Date,
+                "time" => DateTimeFields::Time,
+                _ => DateTimeFields::DateTime,
+            };
+            let length = match parts.next() {
+                None | Some("medium") => DateTimeLength::Medium,
+                Some("short") => DateTimeLength::Short,
+                Some("long") => DateTimeLength::Long,
+                Some("full") => DateTimeLength::Full,
+                Some(length) => {
+                    return Err(invalid(&format!("unknown {} length `{}`", kind, length)))
+                }
+            };
+            if let Some(option) = parts.next() {
+                return Err(invalid(&format!("unexpected option `{}`", option)));
+            }
+            Format::DateTime(DateTimeOptions { fields, length })
+        }
+        Some("list") => {
+            let style = match parts.next() {
+                None | Some("conjunction") => ListStyle::Conjunction,
+                Some("disjunction") => ListStyle::Disjunction,
+                Some("unit") => ListStyle::Unit,
+                Some(style) => return Err(invalid(&format!("unknown list style `{}`", style))),
+            };
+            if let Some(option) = parts.next() {
+                return Err(invalid(&format!("unexpected option `{}`", option)));
+            }
+            Format::List(style)
+        }
+        Some(kind @ ("currency" | "relative_time" | "duration")) => {
+            if let Some(option) = parts.next() {
+                return Err(invalid(&format!("unexpected option `{}`", option)));
+            }
+            match kind {
+                "currency" => Format::Currency,
+                "relative_time" => Format::RelativeTime,
+                _ => Format::Duration,
+            }
+        }
+        Some(format) => return Err(invalid(&format!("unknown format `{}`", format))),
+    };
+
+    Ok((name, format))
+}
+
+/// Finds the names of the markup tags, such as `link` in `Read the <link>terms</link>`.
+///
+/// Only tags with a matching closing tag count, a lone `<br>` or `a < b` is plain text.
+pub fn find_tags(
+    s: &str,
+    start: &str,
+    end: &str,
+    locale_name: &LocaleName,
+) -> Result<HashSet<String>> {
+    let tokens = tokenize(s, start, end, locale_name)?;
+
+    Ok(tokens
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::TagStart(name) => Some(name.to_string()),
+            _ => None,
+        })
+        .collect())
+}
+
+/// A translation split into text and the markup tags wrapping parts of it.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Markup {
+    /// Text, including any `{placeholder}`
+    Text(String),
+    Tag(String, Vec<Markup>),
+}
+
+/// Parses a translation whose placeholders have already been normalized to `{` and `}`.
+pub fn parse_markup(s: &str, locale_name: &LocaleName) -> Result<Vec<Markup>> {
+    let tokens = tokenize(s, "{", "}", locale_name)?;
+
+    // The tokenizer only emits paired tags, so the stack never underflows.
+    let mut stack = vec![(String::new(), Vec::new())];
+    for token in tokens {
+        match token {
+            Token::TagStart(name) => stack.push((name.to_string(), Vec::new())),
+            Token::TagEnd(_) => {
+                let (name, children) = stack.pop().expect("unbalanced tags");
+                push_markup(&mut stack, Markup::Tag(name, children));
+            }
+            Token::PlaceholderStart => push_text(&mut stack, "{"),
+            Token::PlaceholderEnd => push_text(&mut stack, "}"),
+            Token::Char(c) => push_text(&mut stack, c),
+        }
+    }
+
+    Ok(stack.pop().map(|(_, markup)| markup).unwrap_or_default())
+}
+
+fn push_text(stack: &mut [(String, Vec<Markup>)], text: &str) {
+    let (_, children) = stack.last_mut().expect("empty stack");
+    match children.last_mut() {
+        Some(Markup::Text(current)) => current.push_str(text),
+        _ => children.push(Markup::Text(text.to_string())),
+    }
+}
+
+fn push_markup(stack: &mut [(String, Vec<Markup>)], markup: Markup) {
+    let (_, children) = stack.last_mut().expect("empty stack");
+    children.push(markup);
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Token<'a> {
+    PlaceholderStart,
+    PlaceholderEnd,
+    TagStart(&'a str),
+    TagEnd(&'a str),
+    Char(&'a str),
+}
+
+impl Token<'_> {
+    fn is_start(&self) -> bool {
+        matches!(self, Token::PlaceholderStart)
+    }
+
+    fn is_end(&self) -> bool {
+        matches!(self, Token::PlaceholderEnd)
+    }
+
+    fn token(&self) -> &str {
+        match self {
+            Token::PlaceholderStart => panic!("token start"),
+            Token::PlaceholderEnd => panic!("token end"),
+            Token::TagStart(_) => panic!("tag start"),
+            Token::TagEnd(_) => panic!("tag end"),
+            Token::Char(token) => token,
+        }
+    }
+}
+
+pub fn tokenize<'a>(
+    string: &'a str,
+    start: &str,
+    end: &str,
+    locale_name: &LocaleName,
+) -> Result<Vec<Token<'a>>> {
+    let mut tokens = vec![];
+    // (index in `tokens`, index in `s`, length in `s`) of every tag token
+    let mut tags = vec![];
+    let mut inside_placeholder = false;
+    let mut idx = 0;
+    let start = split_into_slices(start);
+    let end = split_into_slices(end);
+    let s = split_into_slices(string);
+    let offsets = string.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+
+    loop {
+        if !within_bounds(&s, idx) {
+            break;
+        }
+
+        if within_bounds(&s, idx + start.len() - 1) {
+            let maybe_start = &s[idx..idx + start.len()];
+            if maybe_start == start.as_slice() {
+                tokens.push(Token::PlaceholderStart);
+                inside_placeholder = true;
+                idx += start.len();
+                continue;
+            }
+        }
+
+        if within_bounds(&s, idx + end.len() - 1) {
+            let maybe_end = &s[idx..idx + end.len()];
+            if maybe_end == end.as_slice() {
+                tokens.push(Token::PlaceholderEnd);
+                inside_placeholder = false;
+                idx += end.len();
+                continue;
+            }
+        }
+
+        if !inside_placeholder {
+            if let Some((token, len)) = tag_at(string, &s, &offsets, idx) {
+                tags.push((tokens.len(), idx, len));
+                tokens.push(token);
+                idx += len;
+                continue;
+            }
+        }
+
+        let c = &s[idx];
+        tokens.push(Token::Char(c));
+        idx += 1;
+    }
+
+    let tokens = unpaired_tags_as_text(tokens, &tags, &s);
+
+    if balanced(&tokens) {
+        Ok(tokens)
+    } else {
+        Err(Error::UnbalancedPlaceholders {
+            locale_name: locale_name.clone(),
+            string: string.to_string(),
+        })
+    }
+}
+
+/// Recognizes `<name>` or `</name>` starting at `idx`.
+fn tag_at<'a>(
+    string: &'a str,
+    s: &[&str],
+    offsets: &[usize],
+    idx: usize,
+) -> Option<(Token<'a>, usize)> {
+    if s[idx] != "<" {
+        return None;
+    }
+
+    let mut name_start = idx + 1;
+    let closing = s.get(name_start) == Some(&"/");
+    if closing {
+        name_start += 1;
+    }
+
+    let mut name_end = name_start;
+    while let Some(c) = s.get(name_end) {
+        let is_name_char = c.chars().all(|c| {
+            c.is_ascii_alphabetic() || c == '_' || (name_end > name_start && c.is_ascii_digit())
+        });
+        if !is_name_char {
+            break;
+        }
+        name_end += 1;
+    }
+
+    if name_end == name_start || s.get(name_end) != Some(&">") {
+        return None;
+    }
+
+    let name = &string[offsets[name_start]..offsets[name_end]];
+    let token = if closing {
+        Token::TagEnd(name)
+    } else {
+        Token::TagStart(name)
+    };
+    Some((token, name_end + 1 - idx))
+}
+
+/// Turns every tag that isn't part of a properly nested pair back into characters.
+fn unpaired_tags_as_text<'a>(
+    tokens: Vec<Token<'a>>,
+    tags: &[(usize, usize, usize)],
+    s: &[&'a str],
+) -> Vec<Token<'a>> {
+    let mut paired = HashSet::new();
+    let mut open: Vec<(usize, &str)> = Vec::new();
+
+    for (token_idx, _, _) in tags {
+        match tokens[*token_idx] {
+            Token::TagStart(name) => open.push((*token_idx, name)),
+            Token::TagEnd(name) => {
+                if let Some(pos) = open.iter().rposition(|(_, open_name)| *open_name == name) {
+                    paired.insert(open[pos].0);
+                    paired.insert(*token_idx);
+                    open.truncate(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if paired.len() == tags.len() {
+        return tokens;
+    }
+
+    let unpaired = tags
+        .iter()
+        .filter(|(token_idx, _, _)| !paired.contains(token_idx))
+        .map(|(token_idx, idx, len)| (*token_idx, (*idx, *len)))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    tokens
+        .into_iter()
+        .enumerate()
+        .flat_map(|(token_idx, token)| match unpaired.get(&token_idx) {
+            Some((idx, len)) => s[*idx..*idx + *len]
+                .iter()
+                .map(|c| Token::Char(c))
+                .collect::<Vec<_>>(),
+            None => vec![token],
+        })
+        .collect()
+}
+
+fn balanced(tokens: &[Token]) -> bool {
+    let mut start_count = 0;
+    let mut end_count = 0;
+    for token in tokens.iter() {
+        if token.is_start() {
+            start_count += 1;
+        } else if token.is_end() {
+            end_count += 1;
+        }
+    }
+    start_count == end_count
+}
+
+fn split_into_slices(s: &str) -> Vec<&str> {
+    let mut v = s.split("").collect::<Vec<&str>>();
+    v.remove(0);
+    v.remove(v.len() - 1);
+    v
+}
+
+fn within_bounds(s: &[&str], idx: usize) -> bool {
+    idx < s.len()
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    macro_rules! hashset {
+        ( $( $item:expr ),* ) => {
+            {
+                let mut s = std::collections::HashSet::new();
+                $( s.insert($item); )*
+                    s
+            }
+        }
+    }
+
+    #[test]
+    fn test_tokenize_empty_input() {
+        assert_eq!(tokenize("", "{", "}", &test_locale()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_tokenize_without_placeholders() {
+        assert_eq!(
+            tokenize("123", "{", "}", &test_locale()).unwrap(),
+            vec![Token::Char("1"), Token::Char("2"), Token::Char("3"),]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_simple_placeholders() {
+        assert_eq!(
+            tokenize("{bob}", "{", "}", &test_locale()).unwrap(),
+            vec![
+                Token::PlaceholderStart,
+                Token::Char("b"),
+                Token::Char("o"),
+                Token::Char("b"),
+                Token::PlaceholderEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_multi_char_placeholders() {
+        assert_eq!(
+            tokenize("[{bob}]", "[{", "}]", &test_locale()).unwrap(),
+            vec![
+                Token::PlaceholderStart,
+                Token::Char("b"),
+                Token::Char("o"),
+                Token::Char("b"),
+                Token::PlaceholderEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_different_length_char_placeholders() {
+        assert_eq!(
+            tokenize("%{bob}", "%{", "}", &test_locale()).unwrap(),
+            vec![
+                Token::PlaceholderStart,
+                Token::Char("b"),
+                Token::Char("o"),
+                Token::Char("b"),
+                Token::PlaceholderEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_emojis() {
+        assert_eq!(
+            tokenize("%{bob} 😅", "%{", "}", &test_locale()).unwrap(),
+            vec![
+                Token::PlaceholderStart,
+                Token::Char("b"),
+                Token::Char("o"),
+                Token::Char("b"),
+                Token::PlaceholderEnd,
+                Token::Char(" "),
+                Token::Char("😅"),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_when_unbalanced() {
+        match tokenize("%{bob", "%{", "}", &test_locale()) {
+            Err(Error::UnbalancedPlaceholders { .. }) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parsing_placeholders() {
+        assert_eq!(
+            placeholder_names("Hello"),
+            HashSet::<String>::new()
+        );
+        assert_eq!(
+            placeholder_names("Hello {name}"),
+            hashset!["name_".to_string()]
+        );
+
+        assert_eq!(
+            placeholder_names("{greeting} {name}"),
+            hashset!["greeting_".to_string(), "name_".to_string()],
+        );
+    }
+
+    #[test]
+    fn test_parsing_placeholder_formats() {
+        let placeholders = find_placeholders(
+            "{name} paid {amount, number, min_fraction=2} ({share, number, percent})",
+            "{",
+            "}",
+            &test_locale(),
+        )
+        .unwrap();
+
+        assert_eq!(placeholders["name_"], Format::Text);
+        assert_eq!(
+            placeholders["amount_"],
+            Format::Number(NumberOptions {
+                min_fraction_digits: Some(2),
+                ..NumberOptions::default()
+            })
+        );
+        assert_eq!(
+            placeholders["share_"],
+            Format::Number(NumberOptions {
+                style: NumberStyle::Percent,
+                ..NumberOptions::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parsing_date_time_formats() {
+        let placeholders = find_placeholders(
+            "{day, date} {start, time, short} {due, datetime, full}",
+            "{",
+            "}",
+            &test_locale(),
+        )
+        .unwrap();
+
+        assert_eq!(placeholders["day_"], Format::DateTime(DateTimeOptions::default()));
+        assert_eq!(
+            placeholders["start_"],
+            Format::DateTime(DateTimeOptions {
+                fields: DateTimeFields::Time,
+                length: DateTimeLength::Short,
+            })
+        );
+        assert_eq!(
+            placeholders["due_"],
+            Format::DateTime(DateTimeOptions {
+                fields: DateTimeFields::DateTime,
+                length: DateTimeLength::Full,
+            })
+        );
+        assert!(find_placeholders("{day, date, tiny}", "{", "}", &test_locale()).is_err());
+        assert_eq!(
+            find_placeholders("{price, currency}", "{", "}", &test_locale()).unwrap()["price_"],
+            Format::Currency
+        );
+        assert_eq!(
+            find_placeholders("{delta, relative_time}", "{", "}", &test_locale()).unwrap()
+                ["delta_"],
+            Format::RelativeTime
+        );
+        assert_eq!(
+            find_placeholders("{names, list, disjunction}", "{", "}", &test_locale()).unwrap()
+                ["names_"],
+            Format::List(ListStyle::Disjunction)
+        );
+    }
+
+    #[test]
+    fn error_on_unknown_format() {
+        match find_placeholders("{amount, numbr}", "{", "}", &test_locale()) {
+            Err(Error::InvalidPlaceholder { .. }) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_normalizing_placeholders() {
+        assert_eq!(
+            normalize_placeholders(
+                "%{name} has <b>%{amount, number}</b>",
+                "%{",
+                "}",
+                &test_locale()
+            )
+            .unwrap(),
+            "{name} has <b>{amount}</b>"
+        );
+    }
+
+    fn placeholder_names(s: &str) -> HashSet<String> {
+        find_placeholders(s, "{", "}", &test_locale())
+            .unwrap()
+            .into_keys()
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_with_tags() {
+        assert_eq!(
+            tokenize("<b>{x}</b>", "{", "}", &test_locale()).unwrap(),
+            vec![
+                Token::TagStart("b"),
+                Token::PlaceholderStart,
+                Token::Char("x"),
+                Token::PlaceholderEnd,
+                Token::TagEnd("b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unpaired_tags_are_text() {
+        assert_eq!(
+            tokenize("<br></i>", "{", "}", &test_locale()).unwrap(),
+            "<br></i>"
+                .split("")
+                .filter(|c| !c.is_empty())
+                .map(Token::Char)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_finding_tags() {
+        assert_eq!(
+            find_tags(
+                "Read the <link>terms</link> and <b>agree</b>, 1 < 2",
+                "{",
+                "}",
+                &test_locale()
+            )
+            .unwrap(),
+            hashset!["link".to_string(), "b".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parsing_markup() {
+        assert_eq!(
+            parse_markup("Hi <b>{name} <i>you</i></b>!", &test_locale()).unwrap(),
+            vec![
+                Markup::Text("Hi ".to_string()),
+                Markup::Tag(
+                    "b".to_string(),
+                    vec![
+                        Markup::Text("{name} ".to_string()),
+                        Markup::Tag("i".to_string(), vec![Markup::Text("you".to_string())]),
+                    ]
+                ),
+                Markup::Text("!".to_string()),
+            ]
+        );
+    }
+
+    fn test_locale() -> LocaleName {
+        LocaleName::new("test")
+    }
+}
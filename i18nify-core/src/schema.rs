@@ -0,0 +1,214 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::Deserialize;
+
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Key(pub String);
+
+#[derive(Debug)]
+pub struct Translation(pub String);
+
+/// Placeholder names, suffixed with `_`, and how each one is formatted.
+#[derive(Debug, Clone)]
+pub struct Placeholders(pub HashMap<String, Format>);
+
+/// The format given after the name of a placeholder, e.g. `{amount, number}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// `{name}`
+    Text,
+    /// `{name, number, ...}`
+    Number(NumberOptions),
+    /// `{name, date|time|datetime, ...}`
+    DateTime(DateTimeOptions),
+    /// `{name, currency}`
+    Currency,
+    /// `{name, relative_time}`
+    RelativeTime,
+    /// `{name, duration}`
+    Duration,
+    /// `{name, list, conjunction|disjunction|unit}`
+    List(ListStyle),
+}
+
+impl Format {
+    /// Formats are compatible when they take the same argument type.
+    pub fn same_kind(&self, other: &Format) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Number(_) => "number",
+            Format::DateTime(_) => "date",
+            Format::Currency => "currency",
+            Format::RelativeTime => "relative time",
+            Format::Duration => "duration",
+            Format::List(_) => "list",
+        }
+    }
+}
+
+/// Mirrors `i18nify::format::NumberStyle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberStyle {
+    #[default]
+    Decimal,
+    Integer,
+    Percent,
+    Compact,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NumberOptions {
+    pub style: NumberStyle,
+    pub min_fraction_digits: Option<u8>,
+    pub max_fraction_digits: Option<u8>,
+}
+
+/// Mirrors `i18nify::format::DateTimeFields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeFields {
+    #[default]
+    Date,
+    Time,
+    DateTime,
+}
+
+/// Mirrors `i18nify::format::DateTimeLength`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeLength {
+    Short,
+    #[default]
+    Medium,
+    Long,
+    Full,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DateTimeOptions {
+    pub fields: DateTimeFields,
+    pub length: DateTimeLength,
+}
+
+/// Mirrors `i18nify::format::ListStyle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListStyle {
+    #[default]
+    Conjunction,
+    Disjunction,
+    Unit,
+}
+
+/// Optional information attached to a translation by using the object form of an entry.
+#[derive(Debug, Default, Clone)]
+pub struct Metadata {
+    pub description: Option<String>,
+    pub context: Option<String>,
+    pub max_length: Option<usize>,
+    /// The translation needs review, e.g. after a change of the source text
+    pub fuzzy: bool,
+}
+
+#[derive(Debug)]
+pub struct I18nKey {
+    /// The key as written in the locale file, before adding the context
+    pub name: String,
+    pub key: Key,
+    pub translation: Translation,
+    pub placeholders: Placeholders,
+    pub metadata: Metadata,
+}
+
+pub type Translations =
+    HashMap<Key, HashMap<LocaleName, (Translation, Placeholders, Metadata)>>;
+
+/// A value in a locale file.
+///
+/// ```json
+/// {
+///     "hello": "Hello",
+///     "save": { "value": "Save", "description": "Button on settings page", "max_length": 12 },
+///     "open": [
+///         { "value": "Open", "context": "verb" },
+///         { "value": "Open", "context": "adjective" }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Entry {
+    Plain(String),
+    Detailed(DetailedEntry),
+    Contextual(Vec<DetailedEntry>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetailedEntry {
+    pub value: String,
+    pub description: Option<String>,
+    pub context: Option<String>,
+    pub max_length: Option<usize>,
+    #[serde(default)]
+    pub fuzzy: bool,
+}
+
+impl Entry {
+    /// Flattens the entry into its values, one per context.
+    pub fn into_values(self) -> Vec<(String, Metadata)> {
+        match self {
+            Entry::Plain(value) => vec![(value, Metadata::default())],
+            Entry::Detailed(entry) => vec![entry.into_value()],
+            Entry::Contextual(entries) => entries
+                .into_iter()
+                .map(DetailedEntry::into_value)
+                .collect(),
+        }
+    }
+}
+
+impl DetailedEntry {
+    fn into_value(self) -> (String, Metadata) {
+        let metadata = Metadata {
+            description: self.description,
+            context: self.context,
+            max_length: self.max_length,
+            fuzzy: self.fuzzy,
+        };
+        (self.value, metadata)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LocaleName(pub String);
+
+impl LocaleName {
+    pub fn new<T: Into<String>>(t: T) -> LocaleName {
+        LocaleName(t.into())
+    }
+}
+
+impl Display for LocaleName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f,"{}",self.0)
+    }
+}
+
+
+/// Syntax of the locale files.
+pub struct Config {
+    pub open: String,
+    pub close: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            open: "{".to_string(),
+            close: "}".to_string(),
+        }
+    }
+}
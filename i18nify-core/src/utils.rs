@@ -0,0 +1,140 @@
+use crate::{
+    error::{Error, Result},
+    schema::{Entry, LocaleName},
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+pub fn find_locale_files<P>(full_locales_path: P) -> Result<Vec<PathBuf>>
+where
+    P: AsRef<Path>,
+{
+    let file_ext = if cfg!(feature = "json") {
+        "json"
+    } else if cfg!(feature = "toml") {
+        "toml"
+    } else {
+        "json"
+    };
+    let paths = std::fs::read_dir(full_locales_path)?
+        .map(|entry| {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                Err(Error::DirectoryInLocalesFolder)
+            } else {
+                Ok(path)
+            }
+        })
+        .filter(|path| match path {
+            Ok(path) => path
+                .extension()
+                .map(|ext| ext == file_ext)
+                .unwrap_or_else(|| false),
+            // don't throw errors away
+            Err(_) => true,
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(paths)
+}
+
+#[allow(clippy::ptr_arg)]
+pub fn locale_name_from_translations_file_path(path: &PathBuf) -> Result<LocaleName> {
+    let file_stem = path
+        .file_stem()
+        .ok_or_else(|| Error::NoFileStem)?
+        .to_str()
+        .ok_or_else(|| Error::InvalidUtf8InFileName)?;
+    let name = uppercase_first_letter(file_stem);
+    Ok(LocaleName(name))
+}
+
+/// BCP 47 language tag for a locale named after its file, e.g. `zh-CN` for `zh_CN.json`.
+pub fn language_tag(locale_name: &LocaleName) -> String {
+    locale_name
+        .0
+        .split(['_', '-'])
+        .enumerate()
+        .map(|(i, subtag)| match subtag.len() {
+            _ if i == 0 => subtag.to_lowercase(),
+            2 | 3 => subtag.to_uppercase(),
+            4 => uppercase_first_letter(&subtag.to_lowercase()),
+            _ => subtag.to_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Name of the methods generated for `key`, e.g. `errors_not_found` for `errors.not-found`.
+pub fn normalize_key(key: &str) -> String {
+    key.replace(".", "_").replace("-", "_")
+}
+
+pub fn uppercase_first_letter(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+    }
+}
+
+#[cfg_attr(not(any(feature = "json", feature = "toml")), allow(unused_variables))]
+pub fn parse_translations_file(contents: &str) -> Result<HashMap<String, Entry>> {
+    
+    #[cfg(feature = "json")]
+    {
+        serde_json::from_str(contents).map_err(From::from)
+    }
+
+    #[cfg(all(feature = "toml", not(feature = "json")))]
+    {
+        toml::from_str(contents).map_err(Error::TomlParsing)
+    }
+    
+    #[cfg(not(any(feature = "json", feature = "toml")))]
+    {
+        Err(Error::UnsupportedFormat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_language_tag() {
+        assert_eq!(language_tag(&LocaleName::new("En")), "en");
+        assert_eq!(language_tag(&LocaleName::new("Zh_CN")), "zh-CN");
+        assert_eq!(language_tag(&LocaleName::new("Sr_latn_rs")), "sr-Latn-RS");
+        assert_eq!(language_tag(&LocaleName::new("Es-419")), "es-419");
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_parse_translations_file() {
+        let contents = r#"
+        hello_world = "Hello, World!"
+        greeting = "Hello {name}"
+        "#;
+        let r = super::parse_translations_file(contents).unwrap();
+        assert!(r.contains_key("hello_world"));
+        assert!(r.contains_key("greeting"));
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_parse_detailed_entries() {
+        let contents = r#"
+        hello_world = "Hello, World!"
+        save = { value = "Save", description = "Button on settings page", max_length = 12 }
+        "#;
+        let mut r = super::parse_translations_file(contents).unwrap();
+        let (value, metadata) = r.remove("save").unwrap().into_values().remove(0);
+        assert_eq!(value, "Save");
+        assert_eq!(metadata.description.as_deref(), Some("Button on settings page"));
+        assert_eq!(metadata.max_length, Some(12));
+    }
+}
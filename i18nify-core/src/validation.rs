@@ -0,0 +1,677 @@
+//! Parsing and validation of the locale files.
+
+use crate::{
+    error::{
+        ContextKeyCollision, Error, MismatchedPlaceholderFormats, MismatchedPlaceholders,
+        MissingKeysInLocale, Result,
+    },
+    placeholder_parsing::{find_placeholders, find_tags, normalize_placeholders, tokenize, Token},
+    schema::{
+        Config, Entry, Format, I18nKey, Key, LocaleName, Metadata, Placeholders, Translation,
+        Translations,
+    },
+    utils::{locale_name_from_translations_file_path, normalize_key, parse_translations_file},
+};
+use heck::ToSnakeCase;
+use rayon::prelude::*;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+pub fn build_translations_from_files(
+    paths_and_contents: &[(&PathBuf, String)],
+    config: &Config,
+) -> Result<Translations> {
+    
+    let keys_per_locale = paths_and_contents
+        .iter()
+        .map(|(path, contents)| {
+            let locale_name = locale_name_from_translations_file_path(path)?;
+            
+            let map = parse_translations_file(contents)?;
+            
+            let keys_in_file = build_keys_from_json(map, config, &locale_name)?;
+
+            let locale_and_keys = keys_in_file
+                .into_iter()
+                .map(|key| (locale_name.clone(), key))
+                .collect::<Vec<(LocaleName, I18nKey)>>();
+            Ok(locale_and_keys)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut keys_per_locale_map: HashMap<(LocaleName, Key), (Translation, Placeholders, Metadata)> =
+        HashMap::new();
+    for (locale, key) in keys_per_locale.into_iter().flatten() {
+        let value = (key.translation, key.placeholders, key.metadata);
+        if keys_per_locale_map
+            .insert((locale.clone(), key.key.clone()), value)
+            .is_some()
+        {
+            return Err(Error::DuplicateKey {
+                locale_name: locale,
+                key: key.key.0,
+            });
+        }
+    }
+    let keys_per_locale = keys_per_locale_map;
+
+    let number_of_keys_per_locale = keys_per_locale.len() / paths_and_contents.len();
+    let mut acc: Translations = HashMap::with_capacity(number_of_keys_per_locale);
+
+    for ((locale_name, key), (translation, placeholders, metadata)) in keys_per_locale {
+        let entry = acc
+            .entry(key)
+            .or_insert_with(|| HashMap::with_capacity(paths_and_contents.len()));
+        entry.insert(locale_name, (translation, placeholders, metadata));
+    }
+
+    Ok(acc)
+}
+
+pub fn build_locale_names_from_files(file_paths: &[PathBuf]) -> Result<Vec<LocaleName>> {
+    file_paths
+        .iter()
+        .map(locale_name_from_translations_file_path)
+        .collect()
+}
+
+pub fn validate_translations(translations: &Translations) -> Result<()> {
+    let all_keys = all_keys(translations);
+    let keys_per_locale = keys_per_locale(translations);
+
+    let mut errors = Vec::new();
+    for (locale_name, keys) in keys_per_locale {
+        let keys_missing = all_keys.difference(&keys).collect::<HashSet<_>>();
+        if !keys_missing.is_empty() {
+            let keys = keys_missing.iter().map(|key| (**key).clone()).collect();
+
+            errors.push(MissingKeysInLocale {
+                locale_name: locale_name.clone(),
+                keys,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::MissingKeysInLocale(errors))
+    }
+}
+
+pub fn all_keys(translations: &Translations) -> HashSet<&Key> {
+    translations.keys().collect()
+}
+
+pub fn keys_per_locale(translations: &Translations) -> HashMap<&LocaleName, HashSet<&Key>> {
+    let mut acc = HashMap::new();
+
+    for (key, translations_for_key) in translations {
+        for (locale_name, (_translation, _placeholders, _metadata)) in translations_for_key {
+            acc.entry(locale_name)
+                .or_insert_with(HashSet::new)
+                .insert(key);
+        }
+    }
+
+    acc
+}
+
+/// The smallest `max_length` any locale declares for a key.
+pub fn max_length(
+    translations_for_key: &HashMap<LocaleName, (Translation, Placeholders, Metadata)>,
+) -> Option<usize> {
+    translations_for_key
+        .values()
+        .filter_map(|(_, _, metadata)| metadata.max_length)
+        .min()
+}
+
+pub fn validate_max_lengths(translations: &Translations) -> Result<()> {
+    for (key, translations_for_key) in translations {
+        let Some(max_length) = max_length(translations_for_key) else {
+            continue;
+        };
+
+        for (locale_name, (translation, _, _)) in translations_for_key {
+            let length = text_length(&translation.0, locale_name)?;
+            if length > max_length {
+                return Err(Error::MaxLengthExceeded {
+                    locale_name: locale_name.clone(),
+                    key: key.0.clone(),
+                    max_length,
+                    length,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A placeholder has to take the same kind of argument in every locale.
+pub fn validate_placeholder_formats(translations: &Translations) -> Result<()> {
+    for (key, translations_for_key) in translations {
+        let mut locales = translations_for_key.iter().collect::<Vec<_>>();
+        locales.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+
+        let mut seen: HashMap<&String, (&LocaleName, &Format)> = HashMap::new();
+        for (locale_name, (_, placeholders, _)) in locales {
+            for (placeholder, format) in &placeholders.0 {
+                match seen.get(placeholder) {
+                    Some((other_locale_name, other_format)) if !format.same_kind(other_format) => {
+                        return Err(Error::MismatchedPlaceholderFormats(Box::new(
+                            MismatchedPlaceholderFormats {
+                                key: key.0.clone(),
+                                placeholder: placeholder.trim_end_matches('_').to_string(),
+                                locale_name: locale_name.clone(),
+                                kind: format.kind(),
+                                other_locale_name: (*other_locale_name).clone(),
+                                other_kind: other_format.kind(),
+                            },
+                        )));
+                    }
+                    Some(_) => {}
+                    None => {
+                        seen.insert(placeholder, (locale_name, format));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Every locale has to use the same placeholder names for a key.
+///
+/// The derive accepts differing names, each method taking the placeholders of all locales, so
+/// only `cargo-i18nify` enforces this to catch misspelled placeholders.
+pub fn validate_placeholder_names(translations: &Translations) -> Result<()> {
+    for (key, translations_for_key) in translations {
+        let mut placeholders_per_locale = translations_for_key
+            .iter()
+            .map(|(locale_name, (_, placeholders, _))| {
+                let mut names = placeholders
+                    .0
+                    .keys()
+                    .map(|name| name.trim_end_matches('_').to_string())
+                    .collect::<Vec<_>>();
+                names.sort();
+                (locale_name, names)
+            })
+            .collect::<Vec<_>>();
+        placeholders_per_locale.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+
+        let mut placeholders_per_locale = placeholders_per_locale.into_iter();
+        let Some((first_locale, first_placeholders)) = placeholders_per_locale.next() else {
+            continue;
+        };
+        for (locale_name, placeholders) in placeholders_per_locale {
+            if placeholders != first_placeholders {
+                return Err(Error::MismatchedPlaceholders(Box::new(
+                    MismatchedPlaceholders {
+                        key: key.0.clone(),
+                        locale_name: locale_name.clone(),
+                        placeholders,
+                        other_locale_name: first_locale.clone(),
+                        other_placeholders: first_placeholders,
+                    },
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Every locale has to use the same markup tags for a key.
+pub fn validate_tags(translations: &Translations) -> Result<()> {
+    for (key, translations_for_key) in translations {
+        let mut tags_per_locale = translations_for_key
+            .iter()
+            .map(|(locale_name, (translation, _, _))| {
+                let mut tags = find_tags(&translation.0, "{", "}", locale_name)?
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                tags.sort();
+                Ok((locale_name, tags))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tags_per_locale.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+
+        let mut tags_per_locale = tags_per_locale.into_iter();
+        let Some((first_locale, first_tags)) = tags_per_locale.next() else {
+            continue;
+        };
+        for (locale_name, tags) in tags_per_locale {
+            if tags != first_tags {
+                return Err(Error::MismatchedTags {
+                    key: key.0.clone(),
+                    locale_name: locale_name.clone(),
+                    tags,
+                    other_locale_name: first_locale.clone(),
+                    other_tags: first_tags,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of characters in a translation, not counting its placeholders and markup tags.
+pub fn text_length(translation: &str, locale_name: &LocaleName) -> Result<usize> {
+    let mut inside_placeholder = false;
+    let mut length = 0;
+    for token in tokenize(translation, "{", "}", locale_name)? {
+        match token {
+            Token::PlaceholderStart => inside_placeholder = true,
+            Token::PlaceholderEnd => inside_placeholder = false,
+            Token::Char(_) if !inside_placeholder => length += 1,
+            Token::Char(_) | Token::TagStart(_) | Token::TagEnd(_) => {}
+        }
+    }
+
+    Ok(length)
+}
+
+pub fn build_keys_from_json(
+    map: HashMap<String, Entry>,
+    config: &Config,
+    locale_name: &LocaleName,
+) -> Result<Vec<I18nKey>> {
+    let mut keys = map
+        .into_par_iter()
+        .flat_map_iter(|(key, entry)| {
+            entry
+                .into_values()
+                .into_iter()
+                .map(move |(value, metadata)| (key.clone(), value, metadata))
+        })
+        .map(|(key, value, metadata)| {
+            let placeholders = find_placeholders(&value, &config.open, &config.close, locale_name)?;
+            let value = normalize_placeholders(&value, &config.open, &config.close, locale_name)?;
+            let name = key;
+            let key = match &metadata.context {
+                Some(context) => format!("{}_{}", name, context.to_snake_case()),
+                None => name.clone(),
+            };
+            let key = normalize_key(&key);
+
+            Ok(I18nKey {
+                name,
+                key: Key(key),
+                translation: Translation(value),
+                placeholders: Placeholders(placeholders),
+                metadata,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    keys.sort_by(|a, b| (&a.name, &a.metadata.context).cmp(&(&b.name, &b.metadata.context)));
+
+    let mut seen: HashMap<&Key, &I18nKey> = HashMap::new();
+    for key in &keys {
+        if let Some(other) = seen.insert(&key.key, key) {
+            return Err(key_collision(
+                locale_name,
+                &key.key,
+                (&key.name, key.metadata.context.as_deref()),
+                (&other.name, other.metadata.context.as_deref()),
+            ));
+        }
+    }
+
+    Ok(keys)
+}
+
+/// The error for two entries of a locale generating the same `key`, given by their names and
+/// contexts as written in the locale file.
+pub fn key_collision(
+    locale_name: &LocaleName,
+    key: &Key,
+    entry: (&str, Option<&str>),
+    other: (&str, Option<&str>),
+) -> Error {
+    let (entry, other) = match entry.1 {
+        Some(_) => (entry, other),
+        None => (other, entry),
+    };
+    match entry {
+        (name, Some(context)) => Error::ContextKeyCollision(Box::new(ContextKeyCollision {
+            locale_name: locale_name.clone(),
+            key: name.to_string(),
+            context: context.to_string(),
+            other_key: other.0.to_string(),
+            other_context: other.1.map(str::to_string),
+        })),
+        (_, None) => Error::DuplicateKey {
+            locale_name: locale_name.clone(),
+            key: key.0.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::NumberOptions;
+    #[cfg(feature = "json")]
+    use std::path::Path;
+
+    #[test]
+    #[cfg(feature="json")]
+    fn test_reading_files() {
+        let input = "../tests/locales";
+        let crate_root_path = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let locale_path = crate_root_path.join(input).join(PathBuf::from("en.json"));
+
+        let contents = std::fs::read_to_string(&locale_path).unwrap();
+        let map = parse_translations_file(&contents).unwrap();
+        let mut keys =
+            build_keys_from_json(map, &Config::default(), &LocaleName::new("test")).unwrap();
+        keys.sort_by_key(|key| key.key.0.clone());
+
+        assert_eq!(keys[0].key.0, "duplicate_placeholders");
+        assert_eq!(keys[0].translation.0, "Hey {name}. Is your name {name}?");
+        assert_eq!(to_vec(keys[0].placeholders.0.keys().cloned().collect()), vec!["name_"]);
+    }
+
+    #[test]
+    #[cfg(feature="json")]
+    fn test_finding_locale_names() {
+        let input = "../tests/locales";
+        let crate_root_path = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let locale_path = crate_root_path.join(input).join(PathBuf::from("en.json"));
+
+        let locale_name = locale_name_from_translations_file_path(&locale_path).unwrap();
+
+        assert_eq!(locale_name.0, "En");
+    }
+
+    #[test]
+    #[cfg(feature="json")]
+    fn test_keys_with_metadata() {
+        let contents = r#"{
+            "save": { "value": "Save", "description": "Button on settings page", "max_length": 12 },
+            "open": [
+                { "value": "Open", "context": "verb" },
+                { "value": "Open", "context": "adjective" }
+            ]
+        }"#;
+        let map = parse_translations_file(contents).unwrap();
+        let mut keys =
+            build_keys_from_json(map, &Config::default(), &LocaleName::new("test")).unwrap();
+        keys.sort_by_key(|key| key.key.0.clone());
+
+        let names = keys.iter().map(|key| key.key.0.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["open_adjective", "open_verb", "save"]);
+        assert_eq!(keys[2].translation.0, "Save");
+        assert_eq!(
+            keys[2].metadata.description.as_deref(),
+            Some("Button on settings page")
+        );
+        assert_eq!(keys[2].metadata.max_length, Some(12));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_context_key_collisions() {
+        let contents = r#"{
+            "open": { "value": "Open", "context": "File" },
+            "open_file": "Open file"
+        }"#;
+        let map = parse_translations_file(contents).unwrap();
+        let error = build_keys_from_json(map, &Config::default(), &LocaleName::new("En"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Key `open` with context `File` collides with the key `open_file`, Locale: En"
+        );
+
+        let contents = r#"{
+            "open": [
+                { "value": "Open", "context": "File" },
+                { "value": "Open", "context": "file" }
+            ]
+        }"#;
+        let map = parse_translations_file(contents).unwrap();
+        let error = build_keys_from_json(map, &Config::default(), &LocaleName::new("En"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Key `open` with context `file` collides with the key `open` with context `File`, Locale: En"
+        );
+
+        let contents = r#"{ "errors.not_found": "Not found", "errors_not_found": "Not found" }"#;
+        let map = parse_translations_file(contents).unwrap();
+        match build_keys_from_json(map, &Config::default(), &LocaleName::new("En")) {
+            Err(Error::DuplicateKey { key, .. }) => assert_eq!(key, "errors_not_found"),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_max_lengths() {
+        let metadata = Metadata {
+            max_length: Some(6),
+            ..Metadata::default()
+        };
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("greeting".to_string()),
+            [
+                ("En", "Hi {name}!", metadata),
+                ("Da", "Goddag {name}!", Metadata::default()),
+            ]
+            .into_iter()
+            .map(|(locale, text, metadata)| {
+                (
+                    LocaleName::new(locale),
+                    (
+                        Translation(text.to_string()),
+                        Placeholders(HashMap::from([("name_".to_string(), Format::Text)])),
+                        metadata,
+                    ),
+                )
+            })
+            .collect(),
+        );
+
+        match validate_max_lengths(&translations) {
+            Err(Error::MaxLengthExceeded {
+                locale_name,
+                max_length: 6,
+                length: 8,
+                ..
+            }) => assert_eq!(locale_name.0, "Da"),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_max_lengths_ignores_tags() {
+        let metadata = Metadata {
+            max_length: Some(10),
+            ..Metadata::default()
+        };
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("terms".to_string()),
+            HashMap::from([(
+                LocaleName::new("En"),
+                (
+                    Translation("Read <link>terms</link>".to_string()),
+                    Placeholders(HashMap::new()),
+                    metadata,
+                ),
+            )]),
+        );
+        assert!(validate_max_lengths(&translations).is_ok());
+
+        translations
+            .get_mut(&Key("terms".to_string()))
+            .unwrap()
+            .get_mut(&LocaleName::new("En"))
+            .unwrap()
+            .0 = Translation("Read the <link>terms</link>".to_string());
+        match validate_max_lengths(&translations) {
+            Err(Error::MaxLengthExceeded {
+                max_length: 10,
+                length: 14,
+                ..
+            }) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_placeholder_formats() {
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("balance".to_string()),
+            [
+                ("En", Format::Number(NumberOptions::default())),
+                ("Da", Format::Text),
+            ]
+            .into_iter()
+            .map(|(locale, format)| {
+                (
+                    LocaleName::new(locale),
+                    (
+                        Translation("{amount}".to_string()),
+                        Placeholders(HashMap::from([("amount_".to_string(), format)])),
+                        Metadata::default(),
+                    ),
+                )
+            })
+            .collect(),
+        );
+
+        match validate_placeholder_formats(&translations) {
+            Err(Error::MismatchedPlaceholderFormats(mismatch)) => {
+                assert_eq!(mismatch.placeholder, "amount");
+                assert_eq!(mismatch.kind, "number");
+                assert_eq!(mismatch.other_kind, "text");
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("terms".to_string()),
+            [
+                ("En", "Read the <link>terms</link>"),
+                ("Da", "Læs <b>betingelserne</b>"),
+            ]
+            .into_iter()
+            .map(|(locale, text)| {
+                (
+                    LocaleName::new(locale),
+                    (
+                        Translation(text.to_string()),
+                        Placeholders(HashMap::new()),
+                        Metadata::default(),
+                    ),
+                )
+            })
+            .collect(),
+        );
+
+        match validate_tags(&translations) {
+            Err(Error::MismatchedTags { tags, other_tags, .. }) => {
+                assert_eq!(tags, vec!["link".to_string()]);
+                assert_eq!(other_tags, vec!["b".to_string()]);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_placeholder_names() {
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("greeting".to_string()),
+            [("En", "name_"), ("De", "nmae_")]
+                .into_iter()
+                .map(|(locale, placeholder)| {
+                    (
+                        LocaleName::new(locale),
+                        (
+                            Translation(format!("{{{}}}", placeholder.trim_end_matches('_'))),
+                            Placeholders(HashMap::from([(placeholder.to_string(), Format::Text)])),
+                            Metadata::default(),
+                        ),
+                    )
+                })
+                .collect(),
+        );
+
+        match validate_placeholder_names(&translations) {
+            Err(Error::MismatchedPlaceholders(mismatch)) => {
+                assert_eq!(mismatch.locale_name.0, "En");
+                assert_eq!(mismatch.placeholders, vec!["name".to_string()]);
+                assert_eq!(mismatch.other_placeholders, vec!["nmae".to_string()]);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn to_vec<T: std::hash::Hash + Eq>(set: HashSet<T>) -> Vec<T> {
+        set.into_iter().collect()
+    }
+
+    #[test]
+    #[cfg(feature="json")]
+    fn test_build_locale_names_from_files()->Result<(), Box<dyn std::error::Error>> {
+
+        let file_paths = &[
+            ("zh_cn",PathBuf::from("../tests/zh_locales/zh_CN.json")),
+           ("en",PathBuf::from("../tests/zh_locales/en.json")),
+        ];
+
+        let paths = file_paths.iter().map(|f| f.1.clone()).collect::<Vec<_>>();
+        let names = file_paths.iter().map(|f| f.0.to_string()).collect::<Vec<_>>();
+
+        let locales = build_locale_names_from_files(&paths).unwrap();
+        locales
+        .iter()
+        .enumerate()
+        // .map(|key| ident(&key.0.to_lower_camel_case())).collect::<Vec<_>>();
+        .for_each(|(index,name)| {
+            assert_eq!(name.0.to_snake_case(),names[index])
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature="toml")]
+    fn test_build_locale_names_from_files()->Result<(), Box<dyn std::error::Error>> {
+
+        let file_paths = &[
+            ("zh_cn",PathBuf::from("../tests/toml_locales/zh_CN.toml")),
+           ("en",PathBuf::from("../tests/toml_locales/en.toml")),
+        ];
+
+        let paths = file_paths.iter().map(|f| f.1.clone()).collect::<Vec<_>>();
+        let names = file_paths.iter().map(|f| f.0.to_string()).collect::<Vec<_>>();
+
+        let locales = build_locale_names_from_files(&paths).unwrap();
+        locales
+        .iter()
+        .enumerate()
+        // .map(|key| ident(&key.0.to_lower_camel_case())).collect::<Vec<_>>();
+        .for_each(|(index,name)| {
+            assert_eq!(name.0.to_snake_case(),names[index])
+        });
+
+        Ok(())
+    }
+}
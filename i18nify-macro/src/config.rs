@@ -0,0 +1,33 @@
+use i18nify_core::schema::Config as LocaleConfig;
+
+/// Options of the `I18N` derive, on top of the syntax of the locale files shared with
+/// `cargo-i18nify`.
+pub(crate) struct Config {
+    pub(crate) locales: LocaleConfig,
+    /// Wrap interpolated values in Unicode isolation marks
+    pub(crate) bidi_isolation: bool,
+    /// Generate `<key>_html` methods escaping the interpolated values
+    pub(crate) html: bool,
+    /// Generate the `CurrentLocaleMessages` trait of the derive target
+    pub(crate) current_messages: bool,
+    /// Generate the `Internationalization` impl, falling back to the locale with this tag
+    pub(crate) default: Option<String>,
+    /// Tags of the pseudo-locales generated from the default locale
+    pub(crate) pseudo: Vec<String>,
+    /// How much longer pseudo-localized translations are, in percent
+    pub(crate) pseudo_expansion: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            locales: LocaleConfig::default(),
+            bidi_isolation: false,
+            html: false,
+            current_messages: false,
+            default: None,
+            pseudo: Vec::new(),
+            pseudo_expansion: 35,
+        }
+    }
+}
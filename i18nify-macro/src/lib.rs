@@ -22,7 +22,7 @@
 //!
 //! And in Rust:
 //!
-//! ```rust
+//! ```rust,ignore
 //! use demo::Internationalize;
 //! mod demo {
 //!     use i18nify::I18N;
@@ -30,47 +30,55 @@
 //!     #[i18n(folder = "tests/doc_locales")]
 //!     pub struct DocLocale;
 //! }
-//! 
-//! fn main() {
-//!     // Based on the `Locale` enum type to retrieve internationalized text
-//!     let hello = demo::Locale::En.hello_world();
-//!     println!("{}",hello);// Hello, World!
-//!     
-//!     // Based on the `Internationalize` trait implemented with `DocLocale` to retrieve internationalized text
-//!     let greeting = DocLocale::da().greeting(Name("John"));
-//!     println!("{}",greeting);// Hej John
-//!}
+//!
+//! // Based on the `Locale` enum type to retrieve internationalized text
+//! let hello = demo::Locale::En.hello_world();
+//! assert_eq!(hello, "Hello, World!");
+//!
+//! // Based on the `Internationalize` trait implemented with `DocLocale` to retrieve internationalized text
+//! let greeting = demo::DocLocale.da().greeting(demo::Name("John"));
+//! assert_eq!(greeting, "Hej John");
 //! ```
 //! 
 
-#![doc(html_root_url = "https://docs.rs/i18nify/0.5.2")]
+#![doc(html_root_url = "https://docs.rs/i18nify-macro/0.4.1")]
 
 // extern crate proc_macro;
 // extern crate proc_macro2;
 
+mod config;
 mod error;
-mod placeholder_parsing;
-mod schema;
-mod utils;
+mod localized_error;
+mod pseudo;
 
-use error::{Error, MissingKeysInLocale, Result};
+use i18nify_core::{completeness, placeholder_parsing, schema, utils, validation};
+
+use completeness::{completeness, Completeness};
+use config::Config;
+use error::{Error, Result};
 use heck::{ToLowerCamelCase, ToSnakeCase, ToUpperCamelCase};
-use placeholder_parsing::find_placeholders;
+use placeholder_parsing::{find_tags, parse_markup, Markup};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use rayon::prelude::*;
-use schema::{Config, I18nKey, Key, LocaleName, Placeholders, Translation, Translations};
+use schema::{
+    Config as LocaleConfig, DateTimeFields, DateTimeLength, DateTimeOptions, Format, Key,
+    ListStyle, LocaleName, Metadata, NumberOptions, NumberStyle, Placeholders, Translation,
+    Translations,
+};
 use std::{
     collections::{HashMap, HashSet},
-    path::{Path, PathBuf},
+    path::Path,
+};
+use syn::{Attribute, DeriveInput, Expr, LitBool, LitInt, LitStr};
+use utils::language_tag;
+use validation::{
+    build_locale_names_from_files, build_translations_from_files, max_length,
+    validate_max_lengths, validate_placeholder_formats, validate_tags, validate_translations,
 };
-use syn::{Attribute, DeriveInput, Expr, LitStr};
-use utils::{locale_name_from_translations_file_path, parse_translations_file};
 
 /// Generates the code for the `Locale` enum and such as the `Locale::hello_world()` methods.
 /// 
-/// ```rust
-/// 
+/// ```rust,ignore
 /// use i18nify::I18N;
 /// 
 /// #[derive(I18N)]
@@ -84,40 +92,70 @@ use utils::{locale_name_from_translations_file_path, parse_translations_file};
 /// ```javascript
 /// //tests/doc_locales/en.json
 /// {
-///     "hello_world": "Hello World!"
+///     "hello_world": "Hello, World!"
 /// }
 /// ```
 /// 
 /// ```javascript
 ///  // tests/doc_locales/da.json
 /// {
-///     "hello_world": "Hej Verden!"
+///     "hello_world": "Hej, Verden!"
 /// }
 /// ```
 /// 
-/// ```rust
-/// use i18nify::{I18N, Locale};
+/// ```rust,ignore
+/// let locale = DocLocale.en();
+/// assert_eq!(locale.hello_world(), "Hello, World!");
 ///
-/// fn main() {
-///     let locale = DocLocale::en();
-///     assert_eq!(locale.hello_world(), "Hello World!");
-/// 
-///     let locale = DocLocale::da();
-///     assert_eq!(locale.hello_world(), "Hej Verden!");
-///}
+/// let locale = DocLocale.da();
+/// assert_eq!(locale.hello_world(), "Hej, Verden!");
+/// ```
 #[proc_macro_derive(I18N, attributes(i18n))]
 pub fn try_i18n(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let DeriveInput { attrs, ident, .. } = syn::parse_macro_input!(input);
     
-    match try_i18n_with_folder2(ident, attrs) {
+    match try_i18n_with_folder2(ident.clone(), attrs) {
         Ok(tokens) => tokens,
+        Err(Error::ProcMacroInput(err)) => err.to_compile_error().into(),
+        Err(err) => syn::Error::new(ident.span(), err).to_compile_error().into(),
+    }
+}
+/// Implements `i18nify::LocalizedError` for an error enum, mapping each variant to a translation
+/// key without placeholders and an HTTP status.
+///
+/// ```rust,ignore
+/// #[derive(Debug, LocalizedError)]
+/// #[localized(locale = Locale, axum)]
+/// pub enum ApiError {
+///     #[localized(key = "user_not_found", status = 404)]
+///     UserNotFound,
+///     #[localized(key = "internal_error", status = 500)]
+///     Database(String),
+/// }
+/// ```
+///
+/// `axum` also implements `IntoResponse`, rendering the error as a problem in the locale of the
+/// request.
+#[proc_macro_derive(LocalizedError, attributes(localized))]
+pub fn derive_localized_error(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input);
+
+    match localized_error::derive_localized_error(input) {
+        Ok(tokens) => tokens.into(),
         Err(err) => panic!("{}", err),
     }
 }
+
 fn try_i18n_with_folder2(ident: Ident, attrs: Vec<Attribute>) -> Result<proc_macro::TokenStream> {
     let mut folder = None;
     let mut start = None;
     let mut end = None;
+    let mut bidi_isolation = None;
+    let mut html = None;
+    let mut current_messages = None;
+    let mut default = None;
+    let mut pseudo = Vec::new();
+    let mut pseudo_expansion = None;
     
     attrs
         .iter()
@@ -130,6 +168,18 @@ fn try_i18n_with_folder2(ident: Ident, attrs: Vec<Attribute>) -> Result<proc_mac
                     start = Some(meta.value()?.parse::<LitStr>()?);
                 } else if meta.path.is_ident("end") {
                     end = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("bidi_isolation") {
+                    bidi_isolation = Some(meta.value()?.parse::<LitBool>()?);
+                } else if meta.path.is_ident("html") {
+                    html = Some(meta.value()?.parse::<LitBool>()?);
+                } else if meta.path.is_ident("current_messages") {
+                    current_messages = Some(meta.value()?.parse::<LitBool>()?);
+                } else if meta.path.is_ident("default") {
+                    default = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("pseudo") {
+                    pseudo.push(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("pseudo_expansion") {
+                    pseudo_expansion = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
                 } else {
                     let _: Option<Expr> = meta.value().and_then(|v| v.parse()).ok();
                 }
@@ -150,8 +200,11 @@ fn try_i18n_with_folder2(ident: Ident, attrs: Vec<Attribute>) -> Result<proc_mac
     ))?.to_string();
 
     let locale_folder = if Path::new(&folder_path).is_relative() {
-        let crate_root_path = Path::new(env!("CARGO_MANIFEST_DIR"));
-        crate_root_path.join(folder)
+        // Resolve against the crate invoking the derive, not this macro crate.
+        let crate_root_path = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| {
+            syn::Error::new(ident.span(), format!("`CARGO_MANIFEST_DIR`: {}", e))
+        })?;
+        Path::new(&crate_root_path).join(folder)
     }else {
         Path::new(&folder_path).to_path_buf()
     };
@@ -167,8 +220,16 @@ fn try_i18n_with_folder2(ident: Ident, attrs: Vec<Attribute>) -> Result<proc_mac
     let start = start.map(|x| x.value()).unwrap_or("{".into());
     let end = end.map(|x| x.value()).unwrap_or("}".into());
     let config = Config {
-        open: start,
-        close: end,
+        locales: LocaleConfig {
+            open: start,
+            close: end,
+        },
+        bidi_isolation: bidi_isolation.is_some_and(|x| x.value()),
+        html: html.is_some_and(|x| x.value()),
+        current_messages: current_messages.is_some_and(|x| x.value()),
+        default: default.map(|x| x.value()),
+        pseudo,
+        pseudo_expansion: pseudo_expansion.unwrap_or(35),
     };
     
     let file_paths = crate::utils::find_locale_files(locale_folder)?;
@@ -176,18 +237,24 @@ fn try_i18n_with_folder2(ident: Ident, attrs: Vec<Attribute>) -> Result<proc_mac
     let paths_and_contents = file_paths
         .iter()
         .map(|path| {
-            let contents = std::fs::read_to_string(path)?;
+            let contents = std::fs::read_to_string(path).map_err(i18nify_core::error::Error::from)?;
             Ok((path, contents))
         })
         .collect::<Result<Vec<_>, Error>>()?;
     
-    let translations = build_translations_from_files(&paths_and_contents, &config)?;
+    let mut translations = build_translations_from_files(&paths_and_contents, &config.locales)?;
     validate_translations(&translations)?;
+    validate_max_lengths(&translations)?;
+    validate_tags(&translations)?;
+    validate_placeholder_formats(&translations)?;
     
-    let locales = build_locale_names_from_files(&file_paths)?;
+    let mut locales = build_locale_names_from_files(&file_paths)?;
+    for tag in &config.pseudo {
+        pseudo::add_pseudo_locale(tag, &config, &mut locales, &mut translations)?;
+    }
     
     let mut output = TokenStream::new();
-    gen_code(ident, locales, translations, &mut output);
+    gen_code(ident, locales, translations, &config, &mut output)?;
     // let syntax_tree: syn::File = syn::parse2(output.clone()).unwrap();
     // let pretty = prettyplease::unparse(&syntax_tree);
 
@@ -198,26 +265,143 @@ fn gen_code(
     ident: Ident,
     locales: Vec<LocaleName>,
     translations: Translations,
+    config: &Config,
     out: &mut TokenStream,
-) {
+) -> Result<()> {
+    validate_unique_tags(&locales)?;
+
+    let source_tag = config.default.as_deref().unwrap_or("en").replace('_', "-");
+    let source = locales
+        .iter()
+        .find(|locale| language_tag(locale).eq_ignore_ascii_case(&source_tag));
+    let completeness = completeness(&translations, &locales, source);
+    let fallback = source.or(locales.first());
+
+    if config.current_messages {
+        validate_current_messages(&translations, &locales, config)?;
+    }
+
     gen_impl_internationalize(&locales, out);
-    gen_locale_enum(&locales, out);
-    gen_i18n_struct(translations, out);
+    gen_locale_enum(&locales, &completeness, out);
+    gen_i18n_struct(&ident, fallback, translations, config, out)?;
+    if let Some(default) = &config.default {
+        gen_impl_internationalization(&ident, &locales, default, out)?;
+    }
     out.extend(quote! {
         impl Internationalize for #ident {}
-    })
+    });
+    Ok(())
+}
+
+/// Files such as `zh_CN.json` and `zh-CN.json` would generate the same `Locale` variant and tag.
+fn validate_unique_tags(locales: &[LocaleName]) -> Result<()> {
+    let mut seen = HashMap::new();
+    for locale_name in locales {
+        let tag = language_tag(locale_name);
+        if let Some(other_locale_name) = seen.insert(tag.to_lowercase(), locale_name) {
+            return Err(Error::DuplicateLanguageTag {
+                tag,
+                locale_name: locale_name.clone(),
+                other_locale_name: other_locale_name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The methods of `CurrentLocaleMessages` must not share a name with the methods of the other
+/// traits of the derive target, or calls to them would be ambiguous.
+fn validate_current_messages(
+    translations: &Translations,
+    locales: &[LocaleName],
+    config: &Config,
+) -> Result<()> {
+    let mut trait_methods = locales
+        .iter()
+        .map(|locale_name| (locale_method_name(locale_name), "Internationalize"))
+        .collect::<Vec<_>>();
+    if config.default.is_some() {
+        trait_methods.extend(
+            ["i", "default_language", "negotiate"]
+                .map(|method| (method.to_string(), "i18nify::Internationalization")),
+        );
+    }
+
+    let mut keys = translations.keys().collect::<Vec<_>>();
+    keys.sort_by(|a, b| a.0.cmp(&b.0));
+    for key in keys {
+        if let Some((_, trait_name)) = trait_methods.iter().find(|(method, _)| *method == key.0) {
+            return Err(Error::CurrentMessageCollision {
+                key: key.0.clone(),
+                trait_name,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Implements `Internationalization` for the derive target, matching the requested languages
+/// against the language tags of the locale files.
+fn gen_impl_internationalization(
+    target: &Ident,
+    locales: &[LocaleName],
+    default: &str,
+    out: &mut TokenStream,
+) -> Result<()> {
+    let tags = locales.iter().map(language_tag).collect::<Vec<_>>();
+    let normalized = default.replace('_', "-").to_lowercase();
+    let default_idx = tags
+        .iter()
+        .position(|tag| tag.to_lowercase() == normalized)
+        .ok_or_else(|| Error::UnknownDefaultLocale {
+            default: default.to_string(),
+            available: tags.clone(),
+        })?;
+    let default_tag = &tags[default_idx];
+    let default_variant = ident(&locales[default_idx].0.to_upper_camel_case());
+    let variants = locales
+        .iter()
+        .map(|locale| ident(&locale.0.to_upper_camel_case()));
+
+    out.extend(quote! {
+        impl ::i18nify::Internationalization for #target {
+            type Item = Locale;
+
+            fn i(&self, lang: &str) -> Locale {
+                self.negotiate(&[lang], #default_tag)
+            }
+
+            fn default_language(&self) -> &str {
+                #default_tag
+            }
+
+            fn negotiate(&self, languages: &[&str], default_lang: &str) -> Locale {
+                const TAGS: &[&str] = &[#(#tags),*];
+                let tag = ::i18nify::accept_language::lookup(languages, TAGS)
+                    .or_else(|| ::i18nify::accept_language::lookup(&[default_lang], TAGS));
+                match tag {
+                    #(Some(#tags) => Locale::#variants,)*
+                    _ => Locale::#default_variant,
+                }
+            }
+        }
+
+        impl ::core::default::Default for Locale {
+            fn default() -> Self {
+                Locale::#default_variant
+            }
+        }
+    });
+    Ok(())
 }
 
 
 
 fn gen_impl_internationalize(locales: &[LocaleName], out: &mut TokenStream) {
     let variants = locales.iter().map(|key| ident(&key.0));
-    let fn_names = locales
-        .iter()
-        .map(|key| ident(&key.0.to_lower_camel_case()));
+    let fn_names = locales.iter().map(|key| ident(&locale_method_name(key)));
 
     let methods = fn_names.zip(variants).map(|(fn_name, variant)| {
-        let fn_name = ident(&fn_name.to_string().to_snake_case());
         let variant = ident(&variant.to_string().to_upper_camel_case());
         quote! {
             fn #fn_name(&self) -> Locale {
@@ -232,11 +416,37 @@ fn gen_impl_internationalize(locales: &[LocaleName], out: &mut TokenStream) {
     });
 }
 
-fn gen_locale_enum(locales: &[LocaleName], out: &mut TokenStream) {
-    let variants = locales.iter().map(|key| {
-        let key = key.0.to_upper_camel_case();
-        ident(&key)
-    });
+/// Name of the method of `Internationalize` giving the locale, e.g. `zh_cn` for `Zh_CN`.
+fn locale_method_name(locale_name: &LocaleName) -> String {
+    locale_name.0.to_lower_camel_case().to_snake_case()
+}
+
+fn gen_locale_enum(locales: &[LocaleName], completeness: &[Completeness], out: &mut TokenStream) {
+    let variants = locales
+        .iter()
+        .map(|key| {
+            let key = key.0.to_upper_camel_case();
+            ident(&key)
+        })
+        .collect::<Vec<_>>();
+    let language_tags = locales.iter().map(language_tag);
+    let completeness = completeness.iter().map(
+        |Completeness {
+             total,
+             missing,
+             fuzzy,
+             identical,
+         }| {
+            quote! {
+                ::i18nify::Completeness {
+                    total: #total,
+                    missing: #missing,
+                    fuzzy: #fuzzy,
+                    identical: #identical,
+                }
+            }
+        },
+    );
 
     out.extend(quote! {
         /// Locale enum generated by "i18nify"
@@ -244,75 +454,153 @@ fn gen_locale_enum(locales: &[LocaleName], out: &mut TokenStream) {
         pub enum Locale {
             #(#variants),*
         }
+
+        impl Locale {
+            /// BCP 47 language tag of the locale, derived from the name of its file.
+            pub fn language_tag(self) -> &'static str {
+                match self {
+                    #(Locale::#variants => #language_tags),*
+                }
+            }
+
+            /// Writing direction of the locale, e.g. for the `dir` attribute of an HTML page.
+            pub fn direction(self) -> ::i18nify::bidi::Direction {
+                ::i18nify::bidi::direction(self.language_tag())
+            }
+
+            /// The locale of the current request, set by `I18nifyLayer` or `i18nify::with_locale`.
+            pub fn current() -> ::core::option::Option<Locale> {
+                ::i18nify::current_locale::<Locale>()
+            }
+
+            /// How many keys of the locale are translated, fuzzy or identical to the default
+            /// locale, `en` without one.
+            pub fn completeness(self) -> ::i18nify::Completeness {
+                match self {
+                    #(Locale::#variants => #completeness),*
+                }
+            }
+        }
+
+        impl ::i18nify::LanguageTag for Locale {
+            fn language_tag(&self) -> &str {
+                Locale::language_tag(*self)
+            }
+        }
     });
 }
 
-fn gen_i18n_struct(translations: Translations, out: &mut TokenStream) {
+/// The arguments shared by every method generated for a key.
+struct MethodArgs {
+    placeholders: Vec<Ident>,
+    args: Vec<TokenStream>,
+    /// Statements converting the `impl Into<..>` arguments once
+    conversions: TokenStream,
+}
+
+/// What is needed to interpolate the placeholders of one locale's translation.
+struct Interpolation<'a> {
+    placeholders: &'a [Ident],
+    formats: &'a HashMap<String, Format>,
+    language_tag: String,
+    /// Wrap the values in Unicode isolation marks
+    isolate: bool,
+    /// HTML-escape the values
+    escape: bool,
+}
+
+/// Generates the message methods of `Locale`, and with `current_messages` the
+/// `CurrentLocaleMessages` trait of the derive target giving the messages in the current locale,
+/// or in `fallback` without one.
+fn gen_i18n_struct(
+    target: &Ident,
+    fallback: Option<&LocaleName>,
+    translations: Translations,
+    config: &Config,
+    out: &mut TokenStream,
+) -> Result<()> {
     let mut all_unique_placeholders = HashSet::<Ident>::new();
+    let key_names = translations
+        .keys()
+        .map(|key| key.0.as_str())
+        .collect::<HashSet<_>>();
 
+    let mut accessors = Vec::new();
     let methods = translations
         .iter()
         .map(|(key, translations)| {
             let name = ident(&key.0);
+            let method_args = method_args(translations);
+
+            for placeholder in &method_args.placeholders {
+                let is_text = translations.values().any(|(_, placeholders, _)| {
+                    placeholders.0.get(&placeholder.to_string()) == Some(&Format::Text)
+                });
+                if is_text {
+                    all_unique_placeholders.insert(placeholder.clone());
+                }
+            }
 
-            let mut placeholders = translations
+            let match_arms = translations
                 .iter()
-                .flat_map(|(_, (_, placeholders))| placeholders.0.iter().map(|p| ident(p)))
-                .collect::<HashSet<_>>()
-                .into_iter()
-                .collect::<Vec<_>>();
-            placeholders.sort();
-
-            for placeholder in &placeholders {
-                all_unique_placeholders.insert(placeholder.clone());
+                .map(|(locale_name, (translation, placeholders, _))| {
+                    let interpolation = Interpolation {
+                        placeholders: &method_args.placeholders,
+                        formats: &placeholders.0,
+                        language_tag: language_tag(locale_name),
+                        isolate: config.bidi_isolation,
+                        escape: false,
+                    };
+                    let locale_name = ident(&locale_name.0.to_upper_camel_case());
+                    let body = format_translation(&translation.0, &interpolation);
+
+                    quote! {
+                        Locale::#locale_name => #body
+                    }
+                });
+            let doc = method_doc(key, &method_args.placeholders, translations);
+            let rich_methods = gen_rich_methods(key, &key_names, translations, &method_args, config)?;
+            let html_method = config
+                .html
+                .then(|| gen_html_method(key, translations, &method_args, config));
+            let MethodArgs {
+                placeholders,
+                args,
+                conversions,
+            } = &method_args;
+
+            if let Some(fallback) = fallback.filter(|_| config.current_messages) {
+                let fallback_variant = ident(&fallback.0.to_upper_camel_case());
+                let accessor_doc = format!(
+                    "[`Locale::{}`] in the [current locale](Locale::current), `{}` without one.",
+                    key.0,
+                    language_tag(fallback)
+                );
+                accessors.push(quote! {
+                    #[doc = #accessor_doc]
+                    fn #name(&self, #(#args),*) -> String {
+                        Locale::current()
+                            .unwrap_or(Locale::#fallback_variant)
+                            .#name(#(#placeholders),*)
+                    }
+                });
             }
 
-            let args = placeholders.iter().map(|placeholder| {
-                let type_name = ident(&placeholder.to_string().to_upper_camel_case());
-                quote! { #placeholder: #type_name<'_> }
-            });
-
-            let match_arms = translations.iter().map(|(locale_name, (translation, _))| {
-                let locale_name = ident(&locale_name.0.to_upper_camel_case());
-                let translation = translation.0.to_string();
-
-                let body = if placeholders.is_empty() {
-                    quote! { format!(#translation) }
-                } else {
-                    let fields = placeholders.iter().filter_map(|placeholder| {
-                        let mut format_key = placeholder.to_string();
-                        format_key.truncate(format_key.len() - 1);
-
-                        let placehoder_with_open_close = format!(
-                            "{open}{placeholder}{close}",
-                            open = "{",
-                            placeholder = format_key,
-                            close = "}",
-                        );
-                        if translation.contains(&placehoder_with_open_close) {
-                            let format_key = ident(&format_key);
-                            Some(quote! { #format_key = #placeholder.0 })
-                        } else {
-                            None
-                        }
-                    });
-                    quote! { format!(#translation, #(#fields),*) }
-                };
-
-                quote! {
-                    Locale::#locale_name => #body
-                }
-            });
-            quote! {
-                #[allow(missing_docs)]
+            Ok(quote! {
+                #[doc = #doc]
                 pub fn #name(self, #(#args),*) -> String {
+                    #conversions
                     match self {
                         #(#match_arms),*
                     }
                 }
-            }
+
+                #rich_methods
+
+                #html_method
+            })
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>>>()?;
 
     let placeholder_newtypes = all_unique_placeholders.into_iter().map(|placeholder| {
         let placeholder = ident(&placeholder.to_string().to_upper_camel_case());
@@ -329,122 +617,505 @@ fn gen_i18n_struct(translations: Translations, out: &mut TokenStream) {
             #(#methods)*
         }
     });
-}
+    if !accessors.is_empty() {
+        out.extend(quote! {
+            /// The messages in the current locale, see [`Locale::current`].
+            pub trait CurrentLocaleMessages {
+                #(#accessors)*
+            }
 
-fn ident(name: &str) -> Ident {
-    Ident::new(name, Span::call_site())
+            impl CurrentLocaleMessages for #target {}
+        });
+    }
+
+    Ok(())
 }
 
-fn build_translations_from_files(
-    paths_and_contents: &[(&PathBuf, String)],
-    config: &Config,
-) -> Result<Translations> {
-    
-    let keys_per_locale = paths_and_contents
+fn method_args(
+    translations: &HashMap<LocaleName, (Translation, Placeholders, Metadata)>,
+) -> MethodArgs {
+    let mut formats = HashMap::new();
+    for (_, placeholders, _) in translations.values() {
+        for (placeholder, format) in &placeholders.0 {
+            formats.entry(placeholder.clone()).or_insert(format);
+        }
+    }
+
+    let mut placeholders = formats.keys().map(|p| ident(p)).collect::<Vec<_>>();
+    placeholders.sort();
+
+    let args = placeholders
         .iter()
-        .map(|(path, contents)| {
-            let locale_name = locale_name_from_translations_file_path(path)?;
-            
-            let map = parse_translations_file(contents)?;
-            
-            let keys_in_file = build_keys_from_json(map, config, &locale_name)?;
-
-            let locale_and_keys = keys_in_file
-                .into_iter()
-                .map(|key| (locale_name.clone(), key))
-                .collect::<Vec<(LocaleName, I18nKey)>>();
-            Ok(locale_and_keys)
+        .map(|placeholder| match formats[&placeholder.to_string()] {
+            Format::Text => {
+                let type_name = ident(&placeholder.to_string().to_upper_camel_case());
+                quote! { #placeholder: #type_name<'_> }
+            }
+            Format::Number(_) => {
+                quote! { #placeholder: impl Into<::i18nify::format::Number> }
+            }
+            Format::DateTime(_) => {
+                quote! { #placeholder: impl Into<::i18nify::format::DateTime> }
+            }
+            Format::Currency => quote! { #placeholder: ::i18nify::format::Money },
+            Format::RelativeTime => {
+                quote! { #placeholder: impl Into<::i18nify::format::RelativeTime> }
+            }
+            Format::Duration => quote! { #placeholder: ::core::time::Duration },
+            Format::List(_) => quote! {
+                #placeholder: impl ::core::iter::IntoIterator<Item = impl ::core::fmt::Display>
+            },
         })
-        .collect::<Result<Vec<_>, Error>>()?;
+        .collect();
 
-    let keys_per_locale: HashMap<(LocaleName, Key), (Translation, Placeholders)> = keys_per_locale
-        .into_iter()
-        .flatten()
-        .map(|(locale, key)| ((locale, key.key), (key.translation, key.placeholders)))
+    let conversions = placeholders
+        .iter()
+        .filter_map(|placeholder| match formats[&placeholder.to_string()] {
+            Format::Text | Format::Currency | Format::Duration => None,
+            Format::Number(_) => Some(quote! {
+                let #placeholder: ::i18nify::format::Number = #placeholder.into();
+            }),
+            Format::DateTime(_) => Some(quote! {
+                let #placeholder: ::i18nify::format::DateTime = #placeholder.into();
+            }),
+            Format::RelativeTime => Some(quote! {
+                let #placeholder: ::i18nify::format::RelativeTime = #placeholder.into();
+            }),
+            Format::List(_) => Some(quote! {
+                let #placeholder: ::std::vec::Vec<::std::string::String> = #placeholder
+                    .into_iter()
+                    .map(|item| ::std::string::ToString::to_string(&item))
+                    .collect();
+            }),
+        })
         .collect();
 
-    let number_of_keys_per_locale = keys_per_locale.len() / paths_and_contents.len();
-    let mut acc: Translations = HashMap::with_capacity(number_of_keys_per_locale);
+    MethodArgs {
+        placeholders,
+        args,
+        conversions,
+    }
+}
 
-    for ((locale_name, key), (translation, placeholders)) in keys_per_locale {
-        let entry = acc
-            .entry(key)
-            .or_insert_with(|| HashMap::with_capacity(paths_and_contents.len()));
-        entry.insert(locale_name, (translation, placeholders));
+/// `format!` call interpolating the placeholders that occur in `translation`.
+fn format_translation(translation: &str, interpolation: &Interpolation) -> TokenStream {
+    if interpolation.placeholders.is_empty() {
+        return quote! { format!(#translation) };
     }
 
-    Ok(acc)
-}
+    let language_tag = &interpolation.language_tag;
+    let fields = interpolation.placeholders.iter().filter_map(|placeholder| {
+        let mut format_key = placeholder.to_string();
+        format_key.truncate(format_key.len() - 1);
+
+        let placehoder_with_open_close = format!(
+            "{open}{placeholder}{close}",
+            open = "{",
+            placeholder = format_key,
+            close = "}",
+        );
+        if !translation.contains(&placehoder_with_open_close) {
+            return None;
+        }
 
-fn build_locale_names_from_files(file_paths: &[PathBuf]) -> Result<Vec<LocaleName>> {
-    file_paths
-        .iter()
-        .map(locale_name_from_translations_file_path)
-        .collect()
+        let format_key = ident(&format_key);
+        let value = match interpolation.formats.get(&placeholder.to_string()) {
+            Some(Format::Number(options)) => {
+                let options = number_options(options);
+                quote! { ::i18nify::format::format_number(#language_tag, #placeholder, &#options) }
+            }
+            Some(Format::DateTime(options)) => {
+                let options = date_time_options(options);
+                quote! { ::i18nify::format::format_date_time(#language_tag, #placeholder, &#options) }
+            }
+            Some(Format::Currency) => {
+                quote! { ::i18nify::format::format_currency(#language_tag, #placeholder) }
+            }
+            Some(Format::RelativeTime) => {
+                quote! { ::i18nify::format::format_relative_time(#language_tag, #placeholder) }
+            }
+            Some(Format::Duration) => {
+                quote! { ::i18nify::format::format_duration(#language_tag, #placeholder) }
+            }
+            Some(Format::List(style)) => {
+                let style = ident(match style {
+                    ListStyle::Conjunction => "Conjunction",
+                    ListStyle::Disjunction => "Disjunction",
+                    ListStyle::Unit => "Unit",
+                });
+                quote! {
+                    ::i18nify::format::format_list(
+                        #language_tag,
+                        &#placeholder,
+                        ::i18nify::format::ListStyle::#style,
+                    )
+                }
+            }
+            Some(Format::Text) | None => quote! { #placeholder.0 },
+        };
+        let value = if interpolation.isolate {
+            quote! { ::i18nify::bidi::Isolate(#value) }
+        } else {
+            value
+        };
+        let value = if interpolation.escape {
+            quote! { ::i18nify::html::Escape(#value) }
+        } else {
+            value
+        };
+        Some(quote! { #format_key = #value })
+    });
+    quote! { format!(#translation, #(#fields),*) }
 }
 
-fn validate_translations(translations: &Translations) -> Result<()> {
-    let all_keys = all_keys(translations);
-    let keys_per_locale = keys_per_locale(translations);
-
-    let mut errors = Vec::new();
-    for (locale_name, keys) in keys_per_locale {
-        let keys_missing = all_keys.difference(&keys).collect::<HashSet<_>>();
-        if !keys_missing.is_empty() {
-            let keys = keys_missing.iter().map(|key| (**key).clone()).collect();
+fn number_options(options: &NumberOptions) -> TokenStream {
+    let style = ident(match options.style {
+        NumberStyle::Decimal => "Decimal",
+        NumberStyle::Integer => "Integer",
+        NumberStyle::Percent => "Percent",
+        NumberStyle::Compact => "Compact",
+    });
+    let min_fraction_digits = option_tokens(options.min_fraction_digits);
+    let max_fraction_digits = option_tokens(options.max_fraction_digits);
+    quote! {
+        ::i18nify::format::NumberOptions {
+            style: ::i18nify::format::NumberStyle::#style,
+            min_fraction_digits: #min_fraction_digits,
+            max_fraction_digits: #max_fraction_digits,
+        }
+    }
+}
 
-            errors.push(MissingKeysInLocale {
-                locale_name: locale_name.clone(),
-                keys,
-            });
+fn date_time_options(options: &DateTimeOptions) -> TokenStream {
+    let fields = ident(match options.fields {
+        DateTimeFields::Date => "Date",
+        DateTimeFields::Time => "Time",
+        DateTimeFields::DateTime => "DateTime",
+    });
+    let length = ident(match options.length {
+        DateTimeLength::Short => "Short",
+        DateTimeLength::Medium => "Medium",
+        DateTimeLength::Long => "Long",
+        DateTimeLength::Full => "Full",
+    });
+    quote! {
+        ::i18nify::format::DateTimeOptions {
+            fields: ::i18nify::format::DateTimeFields::#fields,
+            length: ::i18nify::format::DateTimeLength::#length,
         }
     }
+}
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(Error::MissingKeysInLocale(errors))
+fn option_tokens<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
     }
 }
 
-fn all_keys(translations: &Translations) -> HashSet<&Key> {
-    translations.keys().collect()
+/// Generates `<key>_html`, which HTML-escapes the values but not the translation.
+fn gen_html_method(
+    key: &Key,
+    translations: &HashMap<LocaleName, (Translation, Placeholders, Metadata)>,
+    method_args: &MethodArgs,
+    config: &Config,
+) -> TokenStream {
+    let name = ident(&format!("{}_html", key.0));
+    let match_arms = translations
+        .iter()
+        .map(|(locale_name, (translation, placeholders, _))| {
+            let interpolation = Interpolation {
+                placeholders: &method_args.placeholders,
+                formats: &placeholders.0,
+                language_tag: language_tag(locale_name),
+                isolate: config.bidi_isolation,
+                escape: true,
+            };
+            let locale_name = ident(&locale_name.0.to_upper_camel_case());
+            let body = format_translation(&translation.0, &interpolation);
+
+            quote! {
+                Locale::#locale_name => #body
+            }
+        });
+    let doc = format!(
+        "Like [`Locale::{}`] with the interpolated values HTML-escaped.",
+        key.0
+    );
+    let MethodArgs {
+        args, conversions, ..
+    } = method_args;
+
+    quote! {
+        #[doc = #doc]
+        pub fn #name(self, #(#args),*) -> ::i18nify::html::SafeHtml {
+            #conversions
+            ::i18nify::html::SafeHtml::from_trusted(match self {
+                #(#match_arms),*
+            })
+        }
+    }
 }
 
-fn keys_per_locale(translations: &Translations) -> HashMap<&LocaleName, HashSet<&Key>> {
-    let mut acc = HashMap::new();
+/// Generates `<key>_rich` and `<key>_with` for translations containing markup tags.
+fn gen_rich_methods(
+    key: &Key,
+    key_names: &HashSet<&str>,
+    translations: &HashMap<LocaleName, (Translation, Placeholders, Metadata)>,
+    method_args: &MethodArgs,
+    config: &Config,
+) -> Result<Option<TokenStream>> {
+    let markups = translations
+        .iter()
+        .map(|(locale_name, (translation, placeholders, _))| {
+            let interpolation = Interpolation {
+                placeholders: &method_args.placeholders,
+                formats: &placeholders.0,
+                language_tag: language_tag(locale_name),
+                isolate: config.bidi_isolation,
+                escape: false,
+            };
+            Ok((locale_name, parse_markup(&translation.0, locale_name)?, interpolation))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-    for (key, translations_for_key) in translations {
-        for (locale_name, (_translation, _placeholders)) in translations_for_key {
-            acc.entry(locale_name)
-                .or_insert_with(HashSet::new)
-                .insert(key);
+    // Tags differing only in case, such as `<Link>` and `<link>`, share a closure.
+    let mut tags = markups
+        .iter()
+        .flat_map(|(_, markup, _)| markup_tags(markup))
+        .map(|tag| tag_closure_ident(&tag))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    if tags.is_empty() {
+        return Ok(None);
+    }
+    tags.sort();
+
+    let [rich_name, with_name] = ["rich", "with"].map(|suffix| format!("{}_{}", key.0, suffix));
+    for method in [&rich_name, &with_name] {
+        if key_names.contains(method.as_str()) {
+            return Err(Error::GeneratedMethodCollision {
+                key: key.0.clone(),
+                method: method.clone(),
+            });
         }
     }
+    let (rich_name, with_name) = (ident(&rich_name), ident(&with_name));
+    let MethodArgs {
+        args, conversions, ..
+    } = method_args;
+    let tag_args = tags
+        .iter()
+        .map(|tag| quote! { mut #tag: impl FnMut(String) -> String })
+        .collect::<Vec<_>>();
+
+    let rich_arms = markups.iter().map(|(locale_name, markup, interpolation)| {
+        let locale_name = ident(&locale_name.0.to_upper_camel_case());
+        let calls = markup_renderer_calls(markup, interpolation);
+        quote! {
+            Locale::#locale_name => {
+                #(#calls)*
+            }
+        }
+    });
+    let with_arms = markups.iter().map(|(locale_name, markup, interpolation)| {
+        let locale_name = ident(&locale_name.0.to_upper_camel_case());
+        let body = markup_string(markup, interpolation);
+        quote! {
+            Locale::#locale_name => #body
+        }
+    });
+
+    let rich_doc = format!(
+        "Renders [`Locale::{}`] with a [`RichTextRenderer`](::i18nify::rich::RichTextRenderer).",
+        key.0
+    );
+    let with_doc = format!(
+        "Renders [`Locale::{}`], passing the rendered inner text of each tag to its closure.",
+        key.0
+    );
+
+    Ok(Some(quote! {
+        #[doc = #rich_doc]
+        pub fn #rich_name<R: ::i18nify::rich::RichTextRenderer>(
+            self,
+            mut renderer: R,
+            #(#args),*
+        ) -> R::Output {
+            #conversions
+            match self {
+                #(#rich_arms),*
+            }
+            renderer.finish()
+        }
 
-    acc
+        #[doc = #with_doc]
+        pub fn #with_name(self, #(#args,)* #(#tag_args),*) -> String {
+            #conversions
+            match self {
+                #(#with_arms),*
+            }
+        }
+    }))
 }
 
-fn build_keys_from_json(
-    map: HashMap<String, String>,
-    config: &Config,
-    locale_name: &LocaleName,
-) -> Result<Vec<I18nKey>> {
-    map.into_par_iter()
-        .map(|(key, value)| {
-            let placeholders = find_placeholders(&value, &config.open, &config.close, locale_name)?;
-            let value = value.replace(&config.open, "{").replace(&config.close, "}");
-            let key = key.replace(".", "_").replace("-", "_");
-
-            Ok(I18nKey {
-                key: Key(key),
-                translation: Translation(value),
-                placeholders: Placeholders(placeholders),
-            })
+fn tag_closure_ident(tag: &str) -> Ident {
+    ident(&format!("{}_tag", tag.to_snake_case()))
+}
+
+fn markup_tags(markup: &[Markup]) -> Vec<String> {
+    markup
+        .iter()
+        .flat_map(|markup| match markup {
+            Markup::Text(_) => vec![],
+            Markup::Tag(name, children) => {
+                let mut tags = markup_tags(children);
+                tags.push(name.clone());
+                tags
+            }
+        })
+        .collect()
+}
+
+fn markup_renderer_calls(markup: &[Markup], interpolation: &Interpolation) -> Vec<TokenStream> {
+    markup
+        .iter()
+        .map(|markup| match markup {
+            Markup::Text(text) => {
+                let text = format_translation(text, interpolation);
+                quote! { renderer.text(&#text); }
+            }
+            Markup::Tag(name, children) => {
+                let children = markup_renderer_calls(children, interpolation);
+                quote! {
+                    renderer.start_tag(#name);
+                    #(#children)*
+                    renderer.end_tag(#name);
+                }
+            }
         })
         .collect()
 }
 
+/// Expression building the `String` for `<key>_with`.
+fn markup_string(markup: &[Markup], interpolation: &Interpolation) -> TokenStream {
+    let pieces = markup.iter().map(|markup| match markup {
+        Markup::Text(text) => format_translation(text, interpolation),
+        Markup::Tag(name, children) => {
+            let tag = tag_closure_ident(name);
+            let inner = markup_string(children, interpolation);
+            quote! {{
+                let inner = #inner;
+                #tag(inner)
+            }}
+        }
+    });
+    quote! {{
+        let mut out = String::new();
+        #(out.push_str(&#pieces);)*
+        out
+    }}
+}
+
+/// Longest translation shown in a generated doc comment before it gets truncated.
+const DOC_TRANSLATION_MAX_CHARS: usize = 80;
+
+fn method_doc(
+    key: &Key,
+    placeholders: &[Ident],
+    translations: &HashMap<LocaleName, (Translation, Placeholders, Metadata)>,
+) -> String {
+    let mut locales = translations.iter().collect::<Vec<_>>();
+    locales.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+
+    let mut doc = String::new();
+    if let Some(description) = locales
+        .iter()
+        .find_map(|(_, (_, _, metadata))| metadata.description.as_ref())
+    {
+        doc.push_str(&format!("{}\n\n", description));
+    }
+    doc.push_str(&format!("Translation key: `{}`\n", key.0));
+
+    if let Some(context) = locales
+        .iter()
+        .find_map(|(_, (_, _, metadata))| metadata.context.as_ref())
+    {
+        doc.push_str(&format!("\nContext: `{}`\n", context));
+    }
+    let mut tags = locales
+        .iter()
+        .flat_map(|(locale_name, (translation, _, _))| {
+            find_tags(&translation.0, "{", "}", locale_name).unwrap_or_default()
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|tag| format!("`{}`", tag))
+        .collect::<Vec<_>>();
+    if !tags.is_empty() {
+        tags.sort();
+        doc.push_str(&format!("\nTags: {}\n", tags.join(", ")));
+    }
+    if let Some(max_length) = max_length(translations) {
+        doc.push_str(&format!("\nMax length: {} characters\n", max_length));
+    }
+
+    if !placeholders.is_empty() {
+        let placeholders = placeholders
+            .iter()
+            .map(|placeholder| {
+                let placeholder = placeholder.to_string();
+                let format = locales
+                    .iter()
+                    .find_map(|(_, (_, placeholders, _))| placeholders.0.get(&placeholder));
+                match format {
+                    Some(Format::Text) | None => {
+                        format!("`{}`", placeholder.trim_end_matches('_'))
+                    }
+                    Some(format) => {
+                        format!("`{}` ({})", placeholder.trim_end_matches('_'), format.kind())
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        doc.push_str(&format!("\nPlaceholders: {}\n", placeholders.join(", ")));
+    }
+
+    doc.push('\n');
+    for (locale_name, (translation, _, _)) in locales {
+        doc.push_str(&format!(
+            "- `{}`: {}\n",
+            locale_name.0.to_upper_camel_case(),
+            doc_translation(&translation.0)
+        ));
+    }
+
+    doc
+}
+
+/// Renders a translation as inline code, truncated so long values don't swamp the docs.
+fn doc_translation(translation: &str) -> String {
+    let mut text = translation
+        .chars()
+        .take(DOC_TRANSLATION_MAX_CHARS)
+        .collect::<String>();
+    if translation.chars().count() > DOC_TRANSLATION_MAX_CHARS {
+        text.push('…');
+    }
+    let text = text.replace('\n', "\\n");
+
+    if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+fn ident(name: &str) -> Ident {
+    Ident::new(name, Span::call_site())
+}
+
 #[allow(
     unused_imports,
     dead_code,
@@ -455,41 +1126,9 @@ fn build_keys_from_json(
 )]
 #[cfg(test)]
 mod test {
-    use std::path::Path;
-
     #[allow(unused_imports)]
     use super::*;
 
-    #[test]
-    #[cfg(feature="json")]
-    fn test_reading_files() {
-        let input = "tests/locales";
-        let crate_root_path = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let locale_path = crate_root_path.join(input).join(PathBuf::from("en.json"));
-
-        let contents = std::fs::read_to_string(&locale_path).unwrap();
-        let map = parse_translations_file(&contents).unwrap();
-        let mut keys =
-            build_keys_from_json(map, &Config::default(), &LocaleName::new("test")).unwrap();
-        keys.sort_by_key(|key| key.key.0.clone());
-
-        assert_eq!(keys[0].key.0, "duplicate_placeholders");
-        assert_eq!(keys[0].translation.0, "Hey {name}. Is your name {name}?");
-        assert_eq!(to_vec(keys[0].placeholders.0.clone()), vec!["name_"]);
-    }
-
-    #[test]
-    #[cfg(feature="json")]
-    fn test_finding_locale_names() {
-        let input = "tests/locales";
-        let crate_root_path = Path::new(env!("CARGO_MANIFEST_DIR"));
-        let locale_path = crate_root_path.join(input).join(PathBuf::from("en.json"));
-
-        let locale_name = locale_name_from_translations_file_path(&locale_path).unwrap();
-
-        assert_eq!(locale_name.0, "En");
-    }
-
     #[test]
     fn ui() {
         let t = trybuild::TestCases::new();
@@ -501,55 +1140,191 @@ mod test {
         version_sync::assert_html_root_url_updated!("src/lib.rs");
     }
 
-    fn to_vec<T: std::hash::Hash + Eq>(set: HashSet<T>) -> Vec<T> {
-        set.into_iter().collect()
+    #[test]
+    fn test_method_doc() {
+        let translations = [
+            ("En", "Hello {name}"),
+            ("Da", "Hej {name}"),
+        ]
+        .into_iter()
+        .map(|(locale, text)| {
+            (
+                LocaleName::new(locale),
+                (
+                    Translation(text.to_string()),
+                    Placeholders(HashMap::from([("name_".to_string(), Format::Text)])),
+                    Metadata::default(),
+                ),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+        let doc = method_doc(
+            &Key("greeting".to_string()),
+            &[ident("name_")],
+            &translations,
+        );
+
+        assert_eq!(
+            doc,
+            "Translation key: `greeting`\n\nPlaceholders: `name`\n\n- `Da`: `Hej {name}`\n- `En`: `Hello {name}`\n"
+        );
     }
 
     #[test]
-    #[cfg(feature="json")]
-    fn test_build_locale_names_from_files()->Result<(), Box<dyn std::error::Error>> {
-
-        let file_paths = &[
-            ("zh_cn",PathBuf::from("tests/zh_locales/zh_CN.json")),
-           ("en",PathBuf::from("tests/zh_locales/en.json")),
-        ];
-
-        let paths = file_paths.iter().map(|f| f.1.clone()).collect::<Vec<_>>();
-        let names = file_paths.iter().map(|f| f.0.to_string()).collect::<Vec<_>>();
+    fn test_default_locale_must_exist() {
+        let locales = [LocaleName::new("en"), LocaleName::new("zh_CN")];
+        let mut out = TokenStream::new();
+
+        let target = ident("DocLocale");
+
+        assert!(gen_impl_internationalization(&target, &locales, "zh_cn", &mut out).is_ok());
+        let err = gen_impl_internationalization(&target, &locales, "fr", &mut out).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Unknown default locale `fr`, available: ["en", "zh-CN"]"#
+        );
+    }
 
-        let locales = super::build_locale_names_from_files(&paths).unwrap();
-        locales
-        .iter()
-        .enumerate()
-        // .map(|key| ident(&key.0.to_lower_camel_case())).collect::<Vec<_>>();
-        .for_each(|(index,name)| {
-            assert_eq!(name.0.to_snake_case(),names[index])
-        });
+    #[test]
+    fn test_rich_methods_must_not_collide_with_keys() {
+        let mut translations: Translations = HashMap::new();
+        for (key, text) in [
+            ("terms", "Read the <link>terms</link>"),
+            ("terms_rich", "Terms"),
+        ] {
+            translations.insert(
+                Key(key.to_string()),
+                HashMap::from([(
+                    LocaleName::new("En"),
+                    (
+                        Translation(text.to_string()),
+                        Placeholders(HashMap::new()),
+                        Metadata::default(),
+                    ),
+                )]),
+            );
+        }
 
-        Ok(())
+        let err = gen_i18n_struct(
+            &ident("DocLocale"),
+            None,
+            translations,
+            &Config::default(),
+            &mut TokenStream::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Method `terms_rich` generated for the markup of key `terms` collides with the key `terms_rich`"
+        );
     }
 
     #[test]
-    #[cfg(feature="toml")]
-    fn test_build_locale_names_from_files()->Result<(), Box<dyn std::error::Error>> {
+    fn test_tags_differing_in_case_share_a_closure() {
+        let translations = HashMap::from([(
+            LocaleName::new("En"),
+            (
+                Translation("<Link>Terms</Link> and <link>privacy</link>".to_string()),
+                Placeholders(HashMap::new()),
+                Metadata::default(),
+            ),
+        )]);
+        let method_args = method_args(&translations);
+        let key_names = HashSet::from(["terms"]);
+
+        let tokens = gen_rich_methods(
+            &Key("terms".to_string()),
+            &key_names,
+            &translations,
+            &method_args,
+            &Config::default(),
+        )
+        .unwrap()
+        .unwrap()
+        .to_string();
+        assert_eq!(tokens.matches("mut link_tag").count(), 1);
+    }
 
-        let file_paths = &[
-            ("zh_cn",PathBuf::from("tests/toml_locales/zh_CN.toml")),
-           ("en",PathBuf::from("tests/toml_locales/en.toml")),
-        ];
+    #[test]
+    fn test_current_messages_must_not_collide_with_trait_methods() {
+        let locales = vec![LocaleName::new("En"), LocaleName::new("Da")];
+        let config = Config {
+            default: Some("en".to_string()),
+            current_messages: true,
+            ..Config::default()
+        };
+        let translations = |key: &str| -> Translations {
+            HashMap::from([(
+                Key(key.to_string()),
+                locales
+                    .iter()
+                    .map(|locale_name| {
+                        (
+                            locale_name.clone(),
+                            (
+                                Translation("Hello".to_string()),
+                                Placeholders(HashMap::new()),
+                                Metadata::default(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            )])
+        };
+        let gen = |key: &str, config: &Config| {
+            gen_code(
+                ident("DocLocale"),
+                locales.clone(),
+                translations(key),
+                config,
+                &mut TokenStream::new(),
+            )
+        };
+
+        let err = gen("negotiate", &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Method `negotiate` generated in `CurrentLocaleMessages` for the key `negotiate` collides with `i18nify::Internationalization::negotiate`"
+        );
+        let err = gen("da", &config).unwrap_err();
+        assert!(err.to_string().ends_with("collides with `Internationalize::da`"));
+
+        let config = Config {
+            current_messages: false,
+            ..config
+        };
+        assert!(gen("negotiate", &config).is_ok());
+    }
 
-        let paths = file_paths.iter().map(|f| f.1.clone()).collect::<Vec<_>>();
-        let names = file_paths.iter().map(|f| f.0.to_string()).collect::<Vec<_>>();
+    #[test]
+    fn test_locales_must_have_distinct_tags() {
+        let locales = vec![LocaleName::new("zh_CN"), LocaleName::new("zh-CN")];
+        let config = Config {
+            default: Some("zh-CN".to_string()),
+            ..Config::default()
+        };
+
+        let err = gen_code(
+            ident("DocLocale"),
+            locales,
+            HashMap::new(),
+            &config,
+            &mut TokenStream::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Locales zh-CN and zh_CN have the same language tag `zh-CN`"
+        );
+    }
 
-        let locales = super::build_locale_names_from_files(&paths).unwrap();
-        locales
-        .iter()
-        .enumerate()
-        // .map(|key| ident(&key.0.to_lower_camel_case())).collect::<Vec<_>>();
-        .for_each(|(index,name)| {
-            assert_eq!(name.0.to_snake_case(),names[index])
-        });
+    #[test]
+    fn test_doc_translation_truncates_long_values() {
+        let text = doc_translation(&"a".repeat(DOC_TRANSLATION_MAX_CHARS + 10));
 
-        Ok(())
+        assert_eq!(text, format!("`{}…`", "a".repeat(DOC_TRANSLATION_MAX_CHARS)));
+        assert_eq!(doc_translation("Hello\nWorld!"), "`Hello\\nWorld!`");
+        assert_eq!(doc_translation("use `x`"), "`` use `x` ``");
     }
 }
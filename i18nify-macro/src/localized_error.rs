@@ -0,0 +1,209 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitInt, LitStr, Path};
+
+use crate::{error::Result, utils::normalize_key};
+
+/// Implements `i18nify::LocalizedError` for an enum whose variants name a translation key and an
+/// HTTP status.
+pub(crate) fn derive_localized_error(input: DeriveInput) -> Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    let mut locale = None;
+    let mut axum = false;
+    attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("localized"))
+        .try_for_each(|attr| {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("locale") {
+                    locale = Some(meta.value()?.parse::<Path>()?);
+                } else if meta.path.is_ident("axum") {
+                    axum = true;
+                } else {
+                    return Err(meta.error("expected `locale` or `axum`"));
+                }
+
+                Ok(())
+            })
+        })?;
+    let locale = locale.ok_or_else(|| {
+        syn::Error::new(
+            ident.span(),
+            "expected #[localized(locale = ...)] naming the `Locale` generated by `I18N`",
+        )
+    })?;
+
+    let Data::Enum(data) = data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`LocalizedError` can only be derived for enums",
+        )
+        .into());
+    };
+
+    let mut patterns = Vec::new();
+    let mut keys = Vec::new();
+    let mut methods = Vec::new();
+    let mut statuses = Vec::new();
+    for variant in &data.variants {
+        let mut key = None;
+        let mut status = None;
+        variant
+            .attrs
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("localized"))
+            .try_for_each(|attr| {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("key") {
+                        key = Some(meta.value()?.parse::<LitStr>()?);
+                    } else if meta.path.is_ident("status") {
+                        let lit = meta.value()?.parse::<LitInt>()?;
+                        let code = lit.base10_parse::<u16>()?;
+                        if !(100..=599).contains(&code) {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "expected an HTTP status code",
+                            ));
+                        }
+                        status = Some(code);
+                    } else {
+                        return Err(meta.error("expected `key` or `status`"));
+                    }
+
+                    Ok(())
+                })
+            })?;
+
+        let missing = || {
+            syn::Error::new(
+                variant.ident.span(),
+                "expected #[localized(key = \"...\", status = ...)] on every variant",
+            )
+        };
+        let key = key.ok_or_else(missing)?;
+        let status = status.ok_or_else(missing)?;
+        // The key as written in the locale files, named like the methods of the `I18N` derive
+        let method = syn::parse_str::<Ident>(&normalize_key(&key.value())).map_err(|_| {
+            syn::Error::new(
+                key.span(),
+                "`key` must be a translation key such as `not_found`",
+            )
+        })?;
+
+        let name = &variant.ident;
+        patterns.push(match variant.fields {
+            Fields::Named(_) => quote!(Self::#name { .. }),
+            Fields::Unnamed(_) => quote!(Self::#name(..)),
+            Fields::Unit => quote!(Self::#name),
+        });
+        keys.push(key);
+        methods.push(method);
+        statuses.push(status);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut out = quote! {
+        impl #impl_generics ::i18nify::LocalizedError for #ident #ty_generics #where_clause {
+            type Locale = #locale;
+
+            fn status(&self) -> u16 {
+                match self {
+                    #(#patterns => #statuses,)*
+                }
+            }
+
+            fn key(&self) -> &'static str {
+                match self {
+                    #(#patterns => #keys,)*
+                }
+            }
+
+            fn message(&self, locale: &#locale) -> ::std::string::String {
+                match self {
+                    #(#patterns => locale.#methods(),)*
+                }
+            }
+        }
+    };
+
+    if axum {
+        out.extend(quote! {
+            impl #impl_generics ::i18nify::axum::__private::IntoResponse for #ident #ty_generics #where_clause {
+                fn into_response(self) -> ::i18nify::axum::__private::Response {
+                    ::i18nify::axum::__private::IntoResponse::into_response(::i18nify::axum::Localized(self))
+                }
+            }
+        });
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn derive(input: &str) -> Result<TokenStream> {
+        derive_localized_error(syn::parse_str(input).unwrap())
+    }
+
+    #[test]
+    fn test_requires_key_and_status() {
+        let err = derive(
+            r#"
+            #[localized(locale = Locale)]
+            enum ApiError {
+                #[localized(key = "not_found")]
+                NotFound,
+            }"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("on every variant"));
+
+        let err = derive(
+            r#"
+            #[localized(locale = Locale)]
+            enum ApiError {
+                #[localized(key = "not_found", status = 42)]
+                NotFound,
+            }"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "expected an HTTP status code");
+    }
+
+    #[test]
+    fn test_dotted_keys() {
+        let tokens = derive(
+            r#"
+            #[localized(locale = Locale)]
+            enum ApiError {
+                #[localized(key = "errors.not-found", status = 404)]
+                NotFound,
+            }"#,
+        )
+        .unwrap()
+        .to_string();
+        assert!(tokens.contains("locale . errors_not_found ()"), "{}", tokens);
+        assert!(tokens.contains("\"errors.not-found\""), "{}", tokens);
+    }
+
+    #[test]
+    fn test_requires_locale_and_enum() {
+        let err = derive("enum ApiError {}").unwrap_err();
+        assert!(err.to_string().contains("#[localized(locale = ...)]"));
+
+        let err = derive("#[localized(locale = Locale)] struct ApiError;").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`LocalizedError` can only be derived for enums"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use heck::ToUpperCamelCase;
+
+use crate::{
+    config::Config,
+    error::{Error, Result},
+    placeholder_parsing::{tokenize, Token},
+    schema::{LocaleName, Translation, Translations},
+    utils::{language_tag, uppercase_first_letter},
+};
+
+/// Words appended to lengthen a translation.
+const FILLER: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+];
+
+/// RIGHT-TO-LEFT OVERRIDE and POP DIRECTIONAL FORMATTING, around each run of mirrored text.
+const RLO: char = '\u{202e}';
+const PDF: char = '\u{202c}';
+
+/// Adds a locale tagged `tag`, translated from the default locale (`en` without one).
+///
+/// Tags with the `XB` region mirror the text to test right-to-left layouts, others accent it,
+/// lengthen it by `pseudo_expansion` percent and put it in brackets.
+pub(crate) fn add_pseudo_locale(
+    tag: &str,
+    config: &Config,
+    locales: &mut Vec<LocaleName>,
+    translations: &mut Translations,
+) -> Result<()> {
+    let invalid = |reason: String| Error::InvalidPseudoLocale {
+        tag: tag.to_string(),
+        reason,
+    };
+    let normalize = |tag: &str| tag.replace('_', "-").to_lowercase();
+
+    let source_tag = config.default.as_deref().unwrap_or("en");
+    let source = locales
+        .iter()
+        .find(|locale| normalize(&language_tag(locale)) == normalize(source_tag))
+        .cloned()
+        .ok_or_else(|| invalid(format!("no locale file for its source `{}`", source_tag)))?;
+
+    let pseudo = LocaleName(uppercase_first_letter(&tag.replace('-', "_")));
+    if locales.iter().any(|locale| {
+        normalize(&language_tag(locale)) == normalize(tag)
+            || locale.0.to_upper_camel_case() == pseudo.0.to_upper_camel_case()
+    }) {
+        return Err(invalid("a locale with this name exists".to_string()));
+    }
+
+    let mirror = normalize(tag)
+        .split('-')
+        .skip(1)
+        .any(|subtag| subtag == "xb");
+    for translations_for_key in translations.values_mut() {
+        let Some((translation, placeholders, metadata)) = translations_for_key.get(&source) else {
+            continue;
+        };
+        let translation = if mirror {
+            mirrored(&translation.0, &source)?
+        } else {
+            accented(&translation.0, config.pseudo_expansion, &source)?
+        };
+        let value = (
+            Translation(translation),
+            placeholders.clone(),
+            metadata.clone(),
+        );
+        translations_for_key.insert(pseudo.clone(), value);
+    }
+
+    locales.push(pseudo);
+    Ok(())
+}
+
+/// The runs of text in a normalized translation, the placeholders and markup tags between them
+/// kept as they are.
+fn map_text(
+    translation: &str,
+    locale_name: &LocaleName,
+    mut text: impl FnMut(&str) -> String,
+) -> Result<String> {
+    let mut out = String::with_capacity(translation.len() * 2);
+    let mut run = String::new();
+    let mut inside_placeholder = false;
+
+    for token in tokenize(translation, "{", "}", locale_name)? {
+        if let Token::Char(c) = token {
+            if inside_placeholder {
+                out.push_str(c);
+            } else {
+                run.push_str(c);
+            }
+            continue;
+        }
+
+        if !run.is_empty() {
+            out.push_str(&text(&run));
+            run.clear();
+        }
+        match token {
+            Token::PlaceholderStart => {
+                inside_placeholder = true;
+                out.push('{');
+            }
+            Token::PlaceholderEnd => {
+                inside_placeholder = false;
+                out.push('}');
+            }
+            Token::TagStart(name) => {
+                out.push('<');
+                out.push_str(name);
+                out.push('>');
+            }
+            Token::TagEnd(name) => {
+                out.push_str("</");
+                out.push_str(name);
+                out.push('>');
+            }
+            Token::Char(_) => unreachable!(),
+        }
+    }
+    if !run.is_empty() {
+        out.push_str(&text(&run));
+    }
+
+    Ok(out)
+}
+
+/// `Hello {name}` becomes `[Ĥéļļö {name} one]`.
+fn accented(translation: &str, expansion: u32, locale_name: &LocaleName) -> Result<String> {
+    let mut length = 0;
+    let text = map_text(translation, locale_name, |run| {
+        length += run.chars().count();
+        accent(run)
+    })?;
+
+    let padding = (length * expansion as usize + 50) / 100;
+    let mut filler = String::new();
+    for word in FILLER.iter().cycle() {
+        if filler.chars().count() >= padding {
+            break;
+        }
+        filler.push(' ');
+        filler.push_str(word);
+    }
+
+    Ok(format!("[{}{}]", text, filler))
+}
+
+/// `Hello {name}` becomes `\u{202e}Hello \u{202c}{name}`, the text shown right to left.
+fn mirrored(translation: &str, locale_name: &LocaleName) -> Result<String> {
+    map_text(translation, locale_name, |run| {
+        format!("{}{}{}", RLO, run, PDF)
+    })
+}
+
+/// Accents the letters of `text`, leaving HTML entities such as `&amp;` alone.
+fn accent(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '&' {
+            if let Some(end) = rest.find(';') {
+                let entity = &rest[1..end];
+                let is_entity = !entity.is_empty()
+                    && entity
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '#');
+                if is_entity {
+                    out.push_str(&rest[..=end]);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        out.push(accented_char(c));
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+fn accented_char(c: char) -> char {
+    const LOWER: [char; 26] = [
+        'å', 'ƀ', 'ç', 'ð', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ɱ', 'ñ', 'ö', 'þ', 'ǫ', 'ŕ',
+        'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+    ];
+    const UPPER: [char; 26] = [
+        'Å', 'Ɓ', 'Ç', 'Ð', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ',
+        'Š', 'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+    ];
+
+    match c {
+        'a'..='z' => LOWER[c as usize - 'a' as usize],
+        'A'..='Z' => UPPER[c as usize - 'A' as usize],
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::{Key, Metadata, Placeholders};
+    use std::collections::HashMap;
+
+    fn locale() -> LocaleName {
+        LocaleName::new("En")
+    }
+
+    #[test]
+    fn test_accented() {
+        assert_eq!(
+            accented("Hello {name}", 35, &locale()).unwrap(),
+            "[Ĥéļļö {name} one]"
+        );
+        assert_eq!(
+            accented("Read the <link>terms</link> &amp; more", 0, &locale()).unwrap(),
+            "[Ŕéåð ţĥé <link>ţéŕɱš</link> &amp; ɱöŕé]"
+        );
+        assert_eq!(accented("Save", 100, &locale()).unwrap(), "[Šåṽé one]");
+    }
+
+    #[test]
+    fn test_mirrored() {
+        assert_eq!(
+            mirrored("Hi {name}, <b>welcome</b>", &locale()).unwrap(),
+            "\u{202e}Hi \u{202c}{name}\u{202e}, \u{202c}<b>\u{202e}welcome\u{202c}</b>"
+        );
+    }
+
+    #[test]
+    fn test_add_pseudo_locale() {
+        let mut locales = vec![LocaleName::new("En"), LocaleName::new("Da")];
+        let mut translations: Translations = HashMap::new();
+        translations.insert(
+            Key("save".to_string()),
+            HashMap::from([(
+                LocaleName::new("En"),
+                (
+                    Translation("Save".to_string()),
+                    Placeholders(HashMap::new()),
+                    Metadata::default(),
+                ),
+            )]),
+        );
+
+        add_pseudo_locale("en-XA", &Config::default(), &mut locales, &mut translations).unwrap();
+        assert_eq!(locales[2].0, "En_XA");
+        assert_eq!(language_tag(&locales[2]), "en-XA");
+        let (translation, _, _) = &translations[&Key("save".to_string())][&locales[2]];
+        assert_eq!(translation.0, "[Šåṽé one]");
+
+        let err = add_pseudo_locale("en-XA", &Config::default(), &mut locales, &mut translations)
+            .unwrap_err();
+        assert!(err.to_string().contains("a locale with this name exists"));
+
+        let config = Config {
+            default: Some("fr".to_string()),
+            ..Config::default()
+        };
+        let err = add_pseudo_locale("ar-XB", &config, &mut locales, &mut translations).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid pseudo-locale `ar-XB`: no locale file for its source `fr`"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Parsing of the `Accept-Language` header ([RFC 7231]) and matching of the requested language
+//! ranges against the available locales ([RFC 4647]).
+//!
+//! ```
+//! use i18nify::accept_language::{lookup, parse};
+//!
+//! let ranges = parse("en;q=0.8, de-CH, *;q=0.1, fr;q=0");
+//! let tags = ranges.iter().map(|range| range.tag.as_str()).collect::<Vec<_>>();
+//! assert_eq!(tags, ["de-CH", "en", "*"]);
+//!
+//! assert_eq!(lookup(&tags, &["en", "de"]), Some("de"));
+//! ```
+//!
+//! [RFC 7231]: https://www.rfc-editor.org/rfc/rfc7231#section-5.3.5
+//! [RFC 4647]: https://www.rfc-editor.org/rfc/rfc4647#section-3.4
+
+/// One language range of an `Accept-Language` header with its quality value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageRange {
+    /// The language tag, or `*` for any language
+    pub tag: String,
+    /// Between 0.001 and 1, ranges with `q=0` are left out by [`parse`]
+    pub quality: f32,
+}
+
+/// Parses an `Accept-Language` header into its language ranges, most preferred first.
+///
+/// Ranges with the same quality keep their order. Malformed ranges and ranges with `q=0` are
+/// skipped.
+pub fn parse(header: &str) -> Vec<LanguageRange> {
+    let mut ranges = header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next().filter(|tag| is_language_range(tag))?;
+
+            let mut quality = 1.0;
+            for param in parts {
+                let (name, value) = param.split_once('=')?;
+                if name.trim().eq_ignore_ascii_case("q") {
+                    quality = parse_quality(value.trim())?;
+                }
+            }
+
+            (quality > 0.0).then(|| LanguageRange {
+                tag: tag.to_string(),
+                quality,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // `sort_by` is stable, so equally preferred ranges stay in header order
+    ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+    ranges
+}
+
+/// `*` or 1*8ALPHA *("-" 1*8alphanum)
+fn is_language_range(tag: &str) -> bool {
+    if tag == "*" {
+        return true;
+    }
+
+    tag.split('-').enumerate().all(|(idx, subtag)| {
+        (1..=8).contains(&subtag.len())
+            && subtag.chars().all(|c| {
+                if idx == 0 {
+                    c.is_ascii_alphabetic()
+                } else {
+                    c.is_ascii_alphanumeric() || c == '*'
+                }
+            })
+    })
+}
+
+/// A language tag without wildcards, such as `de` or `zh-Hans-CN`.
+#[cfg(feature = "tower")]
+pub(crate) fn is_language_tag(tag: &str) -> bool {
+    !tag.contains('*') && is_language_range(tag)
+}
+
+/// `0[.ddd]` or `1[.000]`
+fn parse_quality(value: &str) -> Option<f32> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    match integer {
+        "0" => format!("0.{}0", fraction).parse().ok(),
+        "1" if fraction.bytes().all(|b| b == b'0') => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Finds the best available tag for the requested `ranges`, most preferred first.
+///
+/// Each range is tried in turn, dropping its last subtag until an available tag matches, so
+/// `de-CH-1996` tries `de-CH-1996`, `de-CH` and `de`. Tags are compared case-insensitively and
+/// `_` counts as `-`. The `*` range matches nothing here, the caller's default applies instead.
+pub fn lookup<'a>(ranges: &[&str], available: &[&'a str]) -> Option<&'a str> {
+    let normalize = |tag: &str| tag.replace('_', "-").to_ascii_lowercase();
+    let available_normalized = available
+        .iter()
+        .map(|tag| normalize(tag))
+        .collect::<Vec<_>>();
+
+    ranges.iter().filter(|range| **range != "*").find_map(|range| {
+        let mut range = normalize(range);
+        loop {
+            if let Some(idx) = available_normalized.iter().position(|tag| *tag == range) {
+                return Some(available[idx]);
+            }
+
+            let cut = range.rfind('-')?;
+            range.truncate(cut);
+            // A single letter subtag such as `x` in `en-x-twain` needs the one after it
+            if range.rfind('-').is_some_and(|idx| range.len() - idx == 2) {
+                let cut = range.rfind('-')?;
+                range.truncate(cut);
+            }
+        }
+    })
+}
+
+/// Negotiates the locale of `T::default()` for the values of the `Accept-Language` headers of a
+/// request, falling back to its default language. `None` if a value is not visible ASCII.
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub(crate) fn negotiate_default<'a, T>(
+    values: impl IntoIterator<Item = &'a [u8]>,
+) -> Option<T::Item>
+where
+    T: crate::Internationalization + Default,
+{
+    let mut ranges = Vec::new();
+    for value in values {
+        if !value
+            .iter()
+            .all(|b| *b == b'\t' || (b' '..=b'~').contains(b))
+        {
+            return None;
+        }
+        ranges.extend(parse(std::str::from_utf8(value).ok()?));
+    }
+    let languages = ranges
+        .iter()
+        .map(|range| range.tag.as_str())
+        .collect::<Vec<_>>();
+
+    let locale = T::default();
+    Some(locale.negotiate(&languages, locale.default_language()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tags(header: &str) -> Vec<String> {
+        parse(header).into_iter().map(|range| range.tag).collect()
+    }
+
+    #[test]
+    fn test_parse_orders_by_quality() {
+        assert_eq!(
+            tags("zh-CN,zh;q=0.9,en;q=0.8,en-GB;q=0.7,en-US;q=0.6"),
+            ["zh-CN", "zh", "en", "en-GB", "en-US"]
+        );
+        assert_eq!(tags("en;q=0.5, fr, de;q=0.5"), ["fr", "en", "de"]);
+        assert_eq!(parse("en;q=0.8")[0].quality, 0.8);
+    }
+
+    #[test]
+    fn test_parse_single_values_and_whitespace() {
+        assert_eq!(tags("de"), ["de"]);
+        assert_eq!(tags("  da ,  en-gb ; q=0.8 "), ["da", "en-gb"]);
+        assert_eq!(tags("*"), ["*"]);
+        assert!(tags("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_skips_invalid_and_rejected_ranges() {
+        assert_eq!(tags("en;q=0, de;q=0.000, fr"), ["fr"]);
+        assert_eq!(tags("en;q=2, de;q=abc, x-lang-toolong12, 12, fr;q=0.1234, nl"), ["nl"]);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let available = ["en", "en_GB", "zh-Hans", "de"];
+
+        assert_eq!(lookup(&["en-GB"], &available), Some("en_GB"));
+        assert_eq!(lookup(&["en-US"], &available), Some("en"));
+        assert_eq!(lookup(&["fr", "de-CH-1996"], &available), Some("de"));
+        assert_eq!(lookup(&["zh-Hans-CN"], &available), Some("zh-Hans"));
+        assert_eq!(lookup(&["fr", "*"], &available), None);
+    }
+}
@@ -0,0 +1,301 @@
+//! Locale resolution for [`actix-web`](::actix_web), sharing the resolvers of the
+//! [`tower`](crate::tower) layer.
+//!
+//! ```
+//! use actix_web::{web, App};
+//! use i18nify::actix::{I18nify, Lang};
+//! use i18nify::tower::I18nifyLayer;
+//! # use i18nify::Internationalization;
+//! # #[derive(Clone, Default)]
+//! # struct DocLocale;
+//! # impl Internationalization for DocLocale {
+//! #     type Item = String;
+//! #     fn i(&self, lang: &str) -> String { lang.to_string() }
+//! # }
+//!
+//! async fn root(Lang(locale): Lang<DocLocale>) -> String {
+//!     locale
+//! }
+//!
+//! let app = App::new()
+//!     .wrap(I18nify::new(DocLocale, "en"))
+//!     .route("/", web::get().to(root));
+//!
+//! // The resolvers of `I18nifyLayer::builder` work here too
+//! let middleware = I18nify::from(I18nifyLayer::builder(DocLocale, "en").query("lang").build());
+//! ```
+
+use actix_web::{
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderValue},
+        StatusCode,
+    },
+    FromRequest, HttpMessage, HttpRequest, ResponseError,
+};
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+
+pub use crate::lang::{Lang, LangRejection};
+use crate::{
+    tower::{vary_lists, I18nifyLayer},
+    Internationalization,
+};
+
+/// Middleware resolving the locale of each request and storing it in the request extensions,
+/// the actix-web counterpart of [`I18nifyLayer`].
+///
+/// Resolvers get the method, URI and the headers they read, as told by
+/// [`LocaleResolver::headers`](crate::tower::LocaleResolver::headers), of the request. The
+/// actix-web extensions are not visible to them.
+pub struct I18nify<T: Internationalization + Clone>(I18nifyLayer<T>);
+
+impl<T: Internationalization + Clone> I18nify<T> {
+    /// Resolves the locale from the `Accept-Language` header, falling back to `default_lang`.
+    pub fn new<S: AsRef<str>>(locale: T, default_lang: S) -> Self {
+        Self(I18nifyLayer::new(locale, default_lang))
+    }
+}
+
+impl<T: Internationalization + Clone> From<I18nifyLayer<T>> for I18nify<T> {
+    fn from(layer: I18nifyLayer<T>) -> Self {
+        Self(layer)
+    }
+}
+
+impl<T: Internationalization + Clone> Clone for I18nify<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S, B, T> Transform<S, ServiceRequest> for I18nify<T>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+    T: Internationalization + Clone + 'static,
+    T::Item: Clone + Send + Sync + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = I18nifyMiddleware<S, T>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(I18nifyMiddleware {
+            service,
+            headers: self.0.request_headers(),
+            layer: self.0.clone(),
+        }))
+    }
+}
+
+/// The service created by [`I18nify`].
+pub struct I18nifyMiddleware<S, T: Internationalization + Clone> {
+    service: S,
+    layer: I18nifyLayer<T>,
+    /// The headers the resolvers read, `None` for all of them
+    headers: Option<Vec<http::HeaderName>>,
+}
+
+impl<S, B, T> Service<ServiceRequest> for I18nifyMiddleware<S, T>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+    T: Internationalization + Clone,
+    T::Item: Clone + Send + Sync + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let r = self
+            .layer
+            .resolve(&http_parts(request.request(), self.headers.as_deref()));
+        let content_language = self
+            .layer
+            .content_language_of(&r)
+            .and_then(|value| HeaderValue::from_bytes(value.as_bytes()).ok());
+        let vary = self.layer.vary_headers();
+
+        request.extensions_mut().insert(r.clone());
+
+        let future = crate::with_locale(r, self.service.call(request));
+        Box::pin(async move {
+            let mut response = future.await?;
+            let headers = response.headers_mut();
+            if let Some(content_language) = content_language {
+                if !headers.contains_key(header::CONTENT_LANGUAGE) {
+                    headers.insert(header::CONTENT_LANGUAGE, content_language);
+                }
+            }
+            for name in vary {
+                let values = headers
+                    .get_all(header::VARY)
+                    .filter_map(|value| value.to_str().ok());
+                if !vary_lists(values, name.as_str()) {
+                    headers.append(header::VARY, HeaderValue::from_str(name.as_str())?);
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// The method, URI and `headers` of `request` for the resolvers, actix-web using its own `http`
+/// types.
+fn http_parts(request: &HttpRequest, headers: Option<&[http::HeaderName]>) -> http::request::Parts {
+    let (mut parts, ()) = http::Request::new(()).into_parts();
+    parts.method =
+        http::Method::from_bytes(request.method().as_str().as_bytes()).unwrap_or_default();
+    parts.uri = request.uri().to_string().parse().unwrap_or_default();
+
+    let mut copy = |name: http::HeaderName, value: &HeaderValue| {
+        if let Ok(value) = http::HeaderValue::from_bytes(value.as_bytes()) {
+            parts.headers.append(name, value);
+        }
+    };
+    match headers {
+        Some(names) => {
+            for name in names {
+                for value in request.headers().get_all(name.as_str()) {
+                    copy(name.clone(), value);
+                }
+            }
+        }
+        None => {
+            for (name, value) in request.headers() {
+                if let Ok(name) = http::HeaderName::from_bytes(name.as_str().as_bytes()) {
+                    copy(name, value);
+                }
+            }
+        }
+    }
+    parts
+}
+
+impl<T> FromRequest for Lang<T>
+where
+    T: Internationalization + Default,
+    T::Item: Clone + 'static,
+{
+    type Error = LangRejection;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let values = request.headers().get_all(header::ACCEPT_LANGUAGE);
+        ready(Lang::resolve(
+            request.extensions().get::<T::Item>(),
+            values.map(|value| value.as_bytes()),
+        ))
+    }
+}
+
+impl ResponseError for LangRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::{test, web, App, HttpResponse};
+
+    #[derive(Clone, Default)]
+    struct Upper;
+
+    impl Internationalization for Upper {
+        type Item = String;
+
+        fn i(&self, lang: &str) -> String {
+            lang.to_uppercase()
+        }
+    }
+
+    async fn root(Lang(locale): Lang<Upper>) -> HttpResponse {
+        HttpResponse::Ok()
+            .insert_header((header::VARY, "Accept-Encoding"))
+            .body(locale)
+    }
+
+    #[tokio::test]
+    async fn test_middleware() {
+        let layer = I18nifyLayer::builder(Upper, "en")
+            .query("lang")
+            .accept_language()
+            .vary()
+            .build();
+        let app = test::init_service(
+            App::new()
+                .wrap(I18nify::from(layer))
+                .route("/", web::get().to(root)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/?lang=da")
+            .insert_header((header::ACCEPT_LANGUAGE, "de"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        let vary = response
+            .headers()
+            .get_all(header::VARY)
+            .map(|value| value.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vary, ["Accept-Encoding", "accept-language"]);
+        assert_eq!(test::read_body(response).await, "DA");
+    }
+
+    #[test]
+    fn test_http_parts_copies_the_headers_of_the_resolvers() {
+        let request = test::TestRequest::get()
+            .uri("/?lang=da")
+            .insert_header((header::ACCEPT_LANGUAGE, "de"))
+            .insert_header((header::COOKIE, "lang=fr"))
+            .to_http_request();
+
+        let parts = http_parts(&request, Some(&[http::header::ACCEPT_LANGUAGE]));
+        assert_eq!(parts.uri, "/?lang=da");
+        assert_eq!(parts.headers.len(), 1);
+        assert_eq!(parts.headers[http::header::ACCEPT_LANGUAGE], "de");
+
+        let layer = I18nifyLayer::builder(Upper, "en")
+            .accept_language()
+            .query("lang")
+            .build();
+        assert_eq!(
+            layer.request_headers(),
+            Some(vec![http::header::ACCEPT_LANGUAGE])
+        );
+        let layer = I18nifyLayer::builder(Upper, "en")
+            .resolver(crate::tower::from_fn(|_| None))
+            .build();
+        assert_eq!(layer.request_headers(), None);
+        assert_eq!(http_parts(&request, None).headers.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_extractor_without_middleware() {
+        let app = test::init_service(App::new().route("/", web::get().to(root))).await;
+
+        let request = test::TestRequest::get()
+            .insert_header((header::ACCEPT_LANGUAGE, "fr;q=0.5, nl"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(test::read_body(response).await, "NL");
+
+        let request = test::TestRequest::get()
+            .insert_header((
+                header::ACCEPT_LANGUAGE,
+                HeaderValue::from_bytes(b"d\xe9").unwrap(),
+            ))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
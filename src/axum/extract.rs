@@ -0,0 +1,91 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+pub use crate::lang::{Lang, LangRejection};
+use crate::Internationalization;
+
+impl<S, T> FromRequestParts<S> for Lang<T>
+where
+    S: Send + Sync,
+    T: Internationalization + Default,
+    T::Item: Clone + Send + Sync + 'static,
+{
+    type Rejection = LangRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let values = parts.headers.get_all(header::ACCEPT_LANGUAGE);
+        Lang::resolve(
+            parts.extensions.get::<T::Item>(),
+            values.iter().map(|value| value.as_bytes()),
+        )
+    }
+}
+
+impl IntoResponse for LangRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::http::{HeaderValue, Request};
+
+    #[derive(Default)]
+    struct Upper;
+
+    impl Internationalization for Upper {
+        type Item = String;
+
+        fn i(&self, lang: &str) -> String {
+            lang.to_uppercase()
+        }
+    }
+
+    async fn extract(request: Request<()>) -> Result<String, LangRejection> {
+        let (mut parts, _) = request.into_parts();
+        Lang::<Upper>::from_request_parts(&mut parts, &())
+            .await
+            .map(|lang| lang.0)
+    }
+
+    #[tokio::test]
+    async fn test_prefers_layer_extension() {
+        let mut request = Request::builder()
+            .header("accept-language", "de")
+            .body(())
+            .unwrap();
+        request.extensions_mut().insert("FROM-LAYER".to_string());
+
+        assert_eq!(extract(request).await.unwrap(), "FROM-LAYER");
+    }
+
+    #[tokio::test]
+    async fn test_resolves_without_layer() {
+        let request = Request::builder()
+            .header("accept-language", "da;q=0.5, de")
+            .body(())
+            .unwrap();
+        assert_eq!(extract(request).await.unwrap(), "DE");
+
+        let request = Request::builder().body(()).unwrap();
+        assert_eq!(extract(request).await.unwrap(), "EN");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_header() {
+        let request = Request::builder()
+            .header("accept-language", HeaderValue::from_bytes(b"d\xe9").unwrap())
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            extract(request).await,
+            Err(LangRejection::InvalidAcceptLanguage)
+        );
+    }
+}
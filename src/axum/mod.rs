@@ -0,0 +1,36 @@
+//! Locale resolution for [`axum`](::axum), built on the [`tower`](crate::tower) layer.
+//!
+//! ```
+//! use axum::{routing::get, Router};
+//! use i18nify::axum::{I18nifyLayer, Lang};
+//! # use i18nify::Internationalization;
+//! # #[derive(Clone, Default)]
+//! # struct DocLocale;
+//! # impl Internationalization for DocLocale {
+//! #     type Item = String;
+//! #     fn i(&self, lang: &str) -> String { lang.to_string() }
+//! # }
+//!
+//! async fn root(Lang(locale): Lang<DocLocale>) -> String {
+//!     locale
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/", get(root))
+//!     .layer(I18nifyLayer::new(DocLocale, "en"));
+//! ```
+
+mod extract;
+mod problem;
+
+pub use crate::tower::{
+    from_fn, AcceptLanguage, Cookie, FnResolver, Header, I18nifyLayer, I18nifyLayerBuilder,
+    I18nifyService as I18nifyExtractor, LocaleResolver, PathPrefix, QueryParam,
+};
+pub use extract::{Lang, LangRejection};
+pub use problem::Localized;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use axum::response::{IntoResponse, Response};
+}
@@ -0,0 +1,165 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use crate::{current_locale, error::problem_json, LocalizedError};
+
+/// Responds with a [`LocalizedError`] as an `application/problem+json` body.
+///
+/// The message is in the [current locale](crate::current_locale), the one
+/// [`I18nifyLayer`](super::I18nifyLayer) resolved for the request, or in the default locale
+/// without the layer. Deriving `LocalizedError` with
+/// `#[localized(locale = Locale, axum)]` lets handlers return the error itself.
+///
+/// ```
+/// mod demo {
+///     use i18nify::{LocalizedError, I18N};
+///
+///     #[derive(I18N, Clone, Default)]
+///     #[i18n(folder = "tests/error_locales", default = "en")]
+///     pub struct ErrorLocale;
+///
+///     #[derive(Debug, LocalizedError)]
+///     #[localized(locale = Locale, axum)]
+///     pub enum ApiError {
+///         #[localized(key = "user_not_found", status = 404)]
+///         UserNotFound,
+///     }
+/// }
+/// use axum::{routing::get, Router};
+/// use demo::{ApiError, ErrorLocale};
+/// use i18nify::axum::I18nifyLayer;
+///
+/// async fn user() -> Result<String, ApiError> {
+///     Err(ApiError::UserNotFound)
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/user", get(user))
+///     .layer(I18nifyLayer::new(ErrorLocale, "en"));
+/// ```
+#[derive(Debug)]
+pub struct Localized<E>(pub E);
+
+impl<E: LocalizedError> From<E> for Localized<E> {
+    fn from(error: E) -> Self {
+        Localized(error)
+    }
+}
+
+impl<E> IntoResponse for Localized<E>
+where
+    E: LocalizedError,
+    E::Locale: Clone + Default + 'static,
+{
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.0.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let locale = current_locale::<E::Locale>().unwrap_or_default();
+
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            problem_json(&self.0, &locale),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Internationalization;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[derive(Clone, Copy, Default, PartialEq)]
+    enum Locale {
+        #[default]
+        En,
+        De,
+    }
+
+    #[derive(Clone)]
+    struct ErrorLocale;
+
+    impl Internationalization for ErrorLocale {
+        type Item = Locale;
+
+        fn i(&self, lang: &str) -> Locale {
+            if lang == "de" {
+                Locale::De
+            } else {
+                Locale::En
+            }
+        }
+    }
+
+    struct NotFound;
+
+    impl LocalizedError for NotFound {
+        type Locale = Locale;
+
+        fn status(&self) -> u16 {
+            404
+        }
+
+        fn key(&self) -> &'static str {
+            "not_found"
+        }
+
+        fn message(&self, locale: &Locale) -> String {
+            match locale {
+                Locale::En => "Not found".to_string(),
+                Locale::De => "Nicht gefunden".to_string(),
+            }
+        }
+    }
+
+    async fn handler() -> Result<(), Localized<NotFound>> {
+        Err(NotFound.into())
+    }
+
+    async fn body(app: Router, accept_language: &str) -> (StatusCode, String) {
+        let request = Request::builder()
+            .header("accept-language", accept_language)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_problem_in_request_locale() {
+        let app = Router::new()
+            .route("/", get(handler))
+            .layer(crate::tower::I18nifyLayer::new(ErrorLocale, "en"));
+
+        assert_eq!(
+            body(app, "de").await,
+            (
+                StatusCode::NOT_FOUND,
+                r#"{"type":"about:blank","title":"Nicht gefunden","status":404,"code":"not_found"}"#
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_problem_without_layer() {
+        let app = Router::new().route("/", get(handler));
+
+        let (_, body) = body(app, "de").await;
+        assert!(body.contains(r#""title":"Not found""#));
+    }
+}
@@ -0,0 +1,111 @@
+//! Text direction of locales and isolation of interpolated values.
+//!
+//! A right-to-left translation with a left-to-right value inserted into it, or the other way
+//! around, can have its punctuation reordered by the Unicode bidirectional algorithm. With
+//! `bidi_isolation = true` the derive wraps every interpolated value in the invisible
+//! FIRST STRONG ISOLATE and POP DIRECTIONAL ISOLATE marks, so the value is laid out on its own.
+//!
+//! Each generated `Locale` also has a `direction()` to set the `dir` attribute of a page.
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/bidi_locales", bidi_isolation = true)]
+//!     pub struct BidiLocale;
+//! }
+//! use demo::{Locale, Name};
+//! use i18nify::bidi::Direction;
+//!
+//! assert_eq!(Locale::Ar.direction(), Direction::Rtl);
+//! assert_eq!(Locale::En.direction().as_str(), "ltr");
+//! assert_eq!(Locale::Ar.greeting(Name("Ada")), "مرحبا \u{2068}Ada\u{2069}!");
+//! ```
+
+use std::fmt::{self, Display};
+
+/// FIRST STRONG ISOLATE, starts an isolated run whose direction follows its first strong character.
+pub const FSI: char = '\u{2068}';
+
+/// POP DIRECTIONAL ISOLATE, ends the run started by [`FSI`].
+pub const PDI: char = '\u{2069}';
+
+/// The writing direction of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// `ltr` or `rtl`, the values of the HTML `dir` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The direction of the language tag `tag`, right-to-left for languages such as Arabic and
+/// Hebrew and for tags naming a right-to-left script.
+///
+/// ```
+/// use i18nify::bidi::{direction, Direction};
+///
+/// assert_eq!(direction("he"), Direction::Rtl);
+/// assert_eq!(direction("zh-CN"), Direction::Ltr);
+/// assert_eq!(direction("az-Arab"), Direction::Rtl);
+/// ```
+pub fn direction(tag: &str) -> Direction {
+    const RTL_LANGUAGES: &[&str] = &[
+        "ar", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi",
+    ];
+    const RTL_SCRIPTS: &[&str] = &["arab", "hebr", "nkoo", "syrc", "thaa"];
+    const LTR_SCRIPTS: &[&str] = &["cyrl", "latn"];
+
+    let mut subtags = tag.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default();
+    let script = subtags.find(|subtag| subtag.len() == 4);
+
+    let is_script = |scripts: &[&str]| {
+        script.is_some_and(|script| scripts.iter().any(|s| s.eq_ignore_ascii_case(script)))
+    };
+    let rtl = if is_script(RTL_SCRIPTS) {
+        true
+    } else if is_script(LTR_SCRIPTS) {
+        false
+    } else {
+        RTL_LANGUAGES
+            .iter()
+            .any(|rtl| rtl.eq_ignore_ascii_case(language))
+    };
+
+    if rtl {
+        Direction::Rtl
+    } else {
+        Direction::Ltr
+    }
+}
+
+/// Displays its value between [`FSI`] and [`PDI`].
+///
+/// ```
+/// use i18nify::bidi::Isolate;
+///
+/// assert_eq!(Isolate("Ada").to_string(), "\u{2068}Ada\u{2069}");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Isolate<T>(pub T);
+
+impl<T: Display> Display for Isolate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", FSI, self.0, PDI)
+    }
+}
@@ -0,0 +1,60 @@
+//! How much of each locale is translated.
+//!
+//! The generated `Locale::completeness()` counts, among the keys of all the locales, those whose
+//! text is marked `"fuzzy": true` for review and those with the same text as the default locale
+//! (`en` without one), which are likely left untranslated. `cargo i18nify completeness` reports
+//! the same for a folder of locale files, keys missing from a locale included, as text or JSON.
+//!
+//! ```
+//! // tests/completeness_locales/de.json
+//! // {
+//! //     "title": "Einstellungen",
+//! //     "save": { "value": "Sichern", "fuzzy": true },
+//! //     "open": "Öffnen",
+//! //     "ok": "OK"
+//! // }
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/completeness_locales")]
+//!     pub struct DocLocale;
+//! }
+//! use demo::Locale;
+//!
+//! let german = Locale::De.completeness();
+//! assert_eq!((german.total, german.fuzzy, german.identical), (4, 1, 1));
+//! assert_eq!(german.translated(), 2);
+//! assert_eq!(format!("German is {:.0}% translated", german.percent()), "German is 50% translated");
+//! assert_eq!(Locale::En.completeness().percent(), 100.0);
+//! ```
+
+/// The keys of a locale, each counted once: missing, else fuzzy, else identical to the source
+/// text, else translated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Completeness {
+    /// The keys of all the locales
+    pub total: usize,
+    /// Keys without a text in the locale, always 0 for a generated `Locale` since the derive
+    /// requires every key
+    pub missing: usize,
+    /// Keys marked `"fuzzy": true`, to be reviewed
+    pub fuzzy: usize,
+    /// Keys with the same text as the source locale
+    pub identical: usize,
+}
+
+impl Completeness {
+    /// The keys neither missing, fuzzy nor identical to the source text.
+    pub fn translated(&self) -> usize {
+        self.total - self.missing - self.fuzzy - self.identical
+    }
+
+    /// The percentage of translated keys, 100 without any key.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated() as f64 * 100.0 / self.total as f64
+        }
+    }
+}
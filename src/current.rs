@@ -0,0 +1,228 @@
+//! The locale of the current request, for code far from the handler.
+//!
+//! [`with_locale`] sets the locale for a future, as [`I18nifyLayer`] does for every request, and
+//! [`with_locale_sync`] for a closure on the current thread. [`current_locale`] and the generated
+//! `Locale::current()` read it back. Tasks spawned from within do not inherit it.
+//!
+//! ```
+//! use i18nify::{current_locale, with_locale_sync};
+//!
+//! #[derive(Clone, Copy, PartialEq)]
+//! enum Locale {
+//!     En,
+//!     De,
+//! }
+//!
+//! fn greeting() -> &'static str {
+//!     match current_locale::<Locale>() {
+//!         Some(Locale::De) => "Hallo",
+//!         _ => "Hello",
+//!     }
+//! }
+//!
+//! assert_eq!(with_locale_sync(Locale::De, greeting), "Hallo");
+//! assert_eq!(greeting(), "Hello");
+//! ```
+//!
+//! With `current_messages = true` the derive target implements the generated
+//! `CurrentLocaleMessages` trait, with a method per message giving it in the current locale, or in
+//! the default locale (`en` unless set) without one. Keys named like a method of
+//! `Internationalize` or `Internationalization` are rejected at compile time:
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/doc_locales", current_messages = true)]
+//!     pub struct DocLocale;
+//! }
+//! use demo::{CurrentLocaleMessages, DocLocale, Locale, Name};
+//! use i18nify::with_locale_sync;
+//!
+//! assert_eq!(DocLocale.greeting(Name("Ada")), "Hello Ada");
+//! assert_eq!(
+//!     with_locale_sync(Locale::Da, || DocLocale.greeting(Name("Ada"))),
+//!     "Hej Ada"
+//! );
+//! ```
+//!
+//! [`I18nifyLayer`]: crate::tower::I18nifyLayer
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+type Ambient = Arc<dyn Any + Send + Sync>;
+
+thread_local! {
+    static CURRENT_LOCALE: RefCell<Option<Ambient>> = const { RefCell::new(None) };
+}
+
+/// Runs `future` with `locale` as the current locale.
+///
+/// The locale is set around every poll of `future`, on whichever thread polls it, so it works
+/// with any executor.
+///
+/// ```
+/// use i18nify::{current_locale, with_locale};
+///
+/// let future = with_locale("de", async { current_locale::<&str>() });
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// assert_eq!(runtime.block_on(future), Some("de"));
+/// ```
+///
+/// Only `future` itself sees the locale. Tasks it spawns, with `tokio::spawn` or
+/// `tokio::task::spawn_blocking` for example, run outside of it and have no current locale unless
+/// they set it again:
+///
+/// ```
+/// use i18nify::{current_locale, with_locale, with_locale_sync};
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let (spawned, blocking) = runtime.block_on(with_locale("de", async {
+///     let locale = current_locale::<&str>().unwrap();
+///     let spawned = tokio::spawn(with_locale(locale, async { current_locale::<&str>() }));
+///     let blocking = tokio::task::spawn_blocking(move || {
+///         with_locale_sync(locale, current_locale::<&str>)
+///     });
+///     (spawned.await.unwrap(), blocking.await.unwrap())
+/// }));
+/// assert_eq!((spawned, blocking), (Some("de"), Some("de")));
+/// ```
+pub fn with_locale<L, F>(locale: L, future: F) -> impl Future<Output = F::Output>
+where
+    L: Send + Sync + 'static,
+    F: Future,
+{
+    WithLocale {
+        locale: Arc::new(locale),
+        future: Box::pin(future),
+    }
+}
+
+struct WithLocale<F> {
+    locale: Ambient,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithLocale<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let locale = self.locale.clone();
+        scope(locale, || self.future.as_mut().poll(cx))
+    }
+}
+
+/// Runs `f` with `locale` as the current locale of this thread, e.g. in `spawn_blocking`.
+pub fn with_locale_sync<L, R>(locale: L, f: impl FnOnce() -> R) -> R
+where
+    L: Send + Sync + 'static,
+{
+    scope(Arc::new(locale), f)
+}
+
+/// Makes `locale` the current one while `f` runs, restoring the outer one afterwards, even if `f`
+/// panics.
+fn scope<R>(locale: Ambient, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Ambient>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT_LOCALE.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT_LOCALE.with(|current| current.replace(Some(locale)));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// The innermost locale set with [`with_locale_sync`] or [`with_locale`], if it is an `L`.
+///
+/// `None` in tasks spawned from within [`with_locale`], such as with `tokio::spawn` or
+/// `spawn_blocking`, as they do not inherit the locale of the task spawning them.
+pub fn current_locale<L: Clone + 'static>() -> Option<L> {
+    CURRENT_LOCALE.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .and_then(|locale| locale.downcast_ref::<L>().cloned())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sync_scopes_nest_and_restore() {
+        assert_eq!(current_locale::<&str>(), None);
+
+        with_locale_sync("de", || {
+            assert_eq!(current_locale::<&str>(), Some("de"));
+            with_locale_sync("fr", || assert_eq!(current_locale::<&str>(), Some("fr")));
+            assert_eq!(current_locale::<&str>(), Some("de"));
+            assert_eq!(current_locale::<String>(), None);
+        });
+
+        assert_eq!(current_locale::<&str>(), None);
+    }
+
+    #[tokio::test]
+    async fn test_task_scope() {
+        let locale = with_locale("da", async {
+            tokio::task::yield_now().await;
+            current_locale::<&str>()
+        })
+        .await;
+
+        assert_eq!(locale, Some("da"));
+        assert_eq!(current_locale::<&str>(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_scope_inside_task_scope() {
+        let locale = with_locale("da", async {
+            with_locale_sync("fr", || {
+                assert_eq!(current_locale::<&str>(), Some("fr"));
+            });
+            current_locale::<&str>()
+        })
+        .await;
+        assert_eq!(locale, Some("da"));
+    }
+
+    #[tokio::test]
+    async fn test_spawned_tasks_do_not_inherit_the_locale() {
+        let (spawned, blocking) = with_locale("da", async {
+            let spawned = tokio::spawn(async { current_locale::<&str>() });
+            let blocking = tokio::task::spawn_blocking(current_locale::<&str>);
+            (spawned.await.unwrap(), blocking.await.unwrap())
+        })
+        .await;
+
+        assert_eq!(spawned, None);
+        assert_eq!(blocking, None);
+    }
+
+    #[test]
+    fn test_task_scope_inside_sync_scope() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let locale = with_locale_sync("fr", || {
+            runtime.block_on(with_locale("da", async {
+                tokio::task::yield_now().await;
+                current_locale::<&str>()
+            }))
+        });
+        assert_eq!(locale, Some("da"));
+    }
+}
@@ -0,0 +1,111 @@
+//! Errors with localized messages.
+//!
+//! The `LocalizedError` derive maps each variant of an error enum to a translation key and an
+//! HTTP status. The key names a message without placeholders.
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::{LocalizedError, I18N};
+//!
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/error_locales", default = "en")]
+//!     pub struct ErrorLocale;
+//!
+//!     #[derive(Debug, LocalizedError)]
+//!     #[localized(locale = Locale)]
+//!     pub enum ApiError {
+//!         #[localized(key = "user_not_found", status = 404)]
+//!         UserNotFound,
+//!         #[localized(key = "internal_error", status = 500)]
+//!         Database(String),
+//!     }
+//! }
+//! use demo::{ApiError, Locale};
+//! use i18nify::LocalizedError;
+//!
+//! let error = ApiError::UserNotFound;
+//! assert_eq!(error.status(), 404);
+//! assert_eq!(error.message(&Locale::De), "Der Benutzer existiert nicht.");
+//! assert_eq!(
+//!     i18nify::error::problem_json(&error, &Locale::De),
+//!     r#"{"type":"about:blank","title":"Der Benutzer existiert nicht.","status":404,"code":"user_not_found"}"#
+//! );
+//! ```
+
+use std::fmt::Write;
+
+/// An error whose message is translated into the locale of the request.
+pub trait LocalizedError {
+    /// The locale type generated by the `I18N` derive
+    type Locale;
+
+    /// The HTTP status code of the error.
+    fn status(&self) -> u16;
+
+    /// The translation key of the message, also sent as the `code` of problem bodies.
+    fn key(&self) -> &'static str;
+
+    /// The message in `locale`.
+    fn message(&self, locale: &Self::Locale) -> String;
+}
+
+/// Renders `error` as an [RFC 7807] problem, its message in `locale` as the `title`.
+///
+/// [RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+pub fn problem_json<E: LocalizedError + ?Sized>(error: &E, locale: &E::Locale) -> String {
+    let mut json = String::from(r#"{"type":"about:blank","title":"#);
+    push_json_string(&mut json, &error.message(locale));
+    let _ = write!(json, r#","status":{},"code":"#, error.status());
+    push_json_string(&mut json, error.key());
+    json.push('}');
+    json
+}
+
+fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Quoted;
+
+    impl LocalizedError for Quoted {
+        type Locale = ();
+
+        fn status(&self) -> u16 {
+            400
+        }
+
+        fn key(&self) -> &'static str {
+            "quoted"
+        }
+
+        fn message(&self, _: &()) -> String {
+            "Say \"hi\"\n\\ \u{1}".to_string()
+        }
+    }
+
+    #[test]
+    fn test_problem_json_escapes_strings() {
+        assert_eq!(
+            problem_json(&Quoted, &()),
+            r#"{"type":"about:blank","title":"Say \"hi\"\n\\ \u0001","status":400,"code":"quoted"}"#
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use super::{
+    lookup,
+    number::{format_number, minus_sign, Number, NumberOptions},
+};
+
+/// A value for a `{name, currency}` placeholder: an amount in major units and its ISO 4217 code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Money {
+    amount: Number,
+    currency: [u8; 3],
+}
+
+impl Money {
+    /// `None` if `currency` is not a three letter code like `EUR` or `usd`.
+    ///
+    /// ```
+    /// use i18nify::format::Money;
+    ///
+    /// assert_eq!(Money::new(12.5, "eur").unwrap().currency(), "EUR");
+    /// assert!(Money::new(12.5, "euro").is_none());
+    /// ```
+    pub fn new(amount: impl Into<Number>, currency: &str) -> Option<Self> {
+        let code: [u8; 3] = currency.as_bytes().try_into().ok()?;
+        if !code.iter().all(u8::is_ascii_alphabetic) {
+            return None;
+        }
+
+        Some(Self {
+            amount: amount.into(),
+            currency: code.map(|b| b.to_ascii_uppercase()),
+        })
+    }
+
+    pub fn amount(&self) -> Number {
+        self.amount
+    }
+
+    /// The upper case ISO 4217 code.
+    pub fn currency(&self) -> &str {
+        // Only ASCII letters are accepted by `new`
+        std::str::from_utf8(&self.currency).unwrap_or_default()
+    }
+}
+
+/// Formats `money` with the currency symbol, symbol placement and separators of the language
+/// `tag`, using the number of minor unit digits of the currency.
+///
+/// ```
+/// use i18nify::format::{format_currency, Money};
+///
+/// let price = Money::new(1234.5, "EUR").unwrap();
+/// assert_eq!(format_currency("en", price), "€1,234.50");
+/// assert_eq!(format_currency("nl", price), "€\u{a0}1.234,50");
+/// assert_eq!(format_currency("fr", price), "1\u{202f}234,50\u{a0}€");
+/// assert_eq!(format_currency("ja", Money::new(1234.56, "JPY").unwrap()), "￥1,235");
+/// ```
+pub fn format_currency(tag: &str, money: Money) -> String {
+    let data = lookup(CURRENCY_DATA, tag);
+    let code = money.currency();
+    let symbol = data
+        .symbols
+        .iter()
+        .chain(SYMBOLS)
+        .find(|(currency, _)| *currency == code)
+        .map(|(_, symbol)| *symbol)
+        .unwrap_or(code);
+
+    let digits = MINOR_UNITS
+        .iter()
+        .find(|(currency, _)| *currency == code)
+        .map(|(_, digits)| *digits)
+        .unwrap_or(2);
+    let options = NumberOptions {
+        min_fraction_digits: Some(digits),
+        max_fraction_digits: Some(digits),
+        ..NumberOptions::default()
+    };
+    let formatted = format_number(tag, money.amount, &options);
+    let minus = minus_sign(tag);
+    let (sign, number) = match formatted.strip_prefix(minus) {
+        Some(number) => (minus, number),
+        None => ("", formatted.as_str()),
+    };
+
+    // Like CLDR's currency spacing, letters are kept apart from the digits
+    let spacing = |letter: Option<char>| match data.spacing {
+        "" if letter.is_some_and(char::is_alphabetic) => "\u{a0}",
+        spacing => spacing,
+    };
+
+    if data.symbol_first {
+        let spacing = spacing(symbol.chars().last());
+        format!("{}{}{}{}{}", data.prefix, sign, symbol, spacing, number)
+    } else {
+        let spacing = spacing(symbol.chars().next());
+        format!("{}{}{}{}{}", data.prefix, sign, number, spacing, symbol)
+    }
+}
+
+struct CurrencyData {
+    /// `¤#,##0.00` when true, `#,##0.00 ¤` otherwise
+    symbol_first: bool,
+    /// Between the symbol and the number
+    spacing: &'static str,
+    /// Put in front of the whole amount, a right-to-left mark for Arabic
+    prefix: &'static str,
+    /// Symbols differing from [`SYMBOLS`]
+    symbols: &'static [(&'static str, &'static str)],
+}
+
+const SYMBOL_FIRST: CurrencyData = CurrencyData {
+    symbol_first: true,
+    spacing: "",
+    prefix: "",
+    symbols: &[],
+};
+
+const SYMBOL_LAST: CurrencyData = CurrencyData {
+    symbol_first: false,
+    spacing: "\u{a0}",
+    prefix: "",
+    symbols: &[],
+};
+
+/// English symbols, used unless a language overrides them. Other codes are shown as is.
+const SYMBOLS: &[(&str, &str)] = &[
+    ("AUD", "A$"),
+    ("BRL", "R$"),
+    ("CAD", "CA$"),
+    ("CNY", "CN¥"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("HKD", "HK$"),
+    ("ILS", "₪"),
+    ("INR", "₹"),
+    ("JPY", "¥"),
+    ("KRW", "₩"),
+    ("MXN", "MX$"),
+    ("NZD", "NZ$"),
+    ("TWD", "NT$"),
+    ("USD", "$"),
+    ("VND", "₫"),
+];
+
+/// Currencies without two minor unit digits.
+const MINOR_UNITS: &[(&str, u8)] = &[
+    ("BHD", 3),
+    ("CLP", 0),
+    ("IQD", 0),
+    ("ISK", 0),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("LYD", 3),
+    ("OMR", 3),
+    ("PYG", 0),
+    ("TND", 3),
+    ("UGX", 0),
+    ("VND", 0),
+];
+
+static CURRENCY_DATA: &[(&str, CurrencyData)] = &[
+    ("en", SYMBOL_FIRST),
+    (
+        "ar",
+        CurrencyData {
+            prefix: "\u{200f}",
+            symbols: &[("USD", "US$")],
+            ..SYMBOL_LAST
+        },
+    ),
+    (
+        "da",
+        CurrencyData {
+            symbols: &[("DKK", "kr."), ("USD", "US$")],
+            ..SYMBOL_LAST
+        },
+    ),
+    ("de", SYMBOL_LAST),
+    (
+        "es",
+        CurrencyData {
+            symbols: &[("USD", "US$")],
+            ..SYMBOL_LAST
+        },
+    ),
+    (
+        "fr",
+        CurrencyData {
+            symbols: &[("USD", "$US"), ("CAD", "$CA")],
+            ..SYMBOL_LAST
+        },
+    ),
+    ("hi", SYMBOL_FIRST),
+    (
+        "it",
+        CurrencyData {
+            symbols: &[("USD", "USD")],
+            ..SYMBOL_LAST
+        },
+    ),
+    (
+        "ja",
+        CurrencyData {
+            symbols: &[("CNY", "元"), ("JPY", "￥")],
+            ..SYMBOL_FIRST
+        },
+    ),
+    (
+        "ko",
+        CurrencyData {
+            symbols: &[("USD", "US$")],
+            ..SYMBOL_FIRST
+        },
+    ),
+    (
+        "nl",
+        CurrencyData {
+            spacing: "\u{a0}",
+            symbols: &[("USD", "US$")],
+            ..SYMBOL_FIRST
+        },
+    ),
+    (
+        "pt",
+        CurrencyData {
+            spacing: "\u{a0}",
+            symbols: &[("USD", "US$")],
+            ..SYMBOL_FIRST
+        },
+    ),
+    (
+        "ru",
+        CurrencyData {
+            symbols: &[("RUB", "₽"), ("USD", "$")],
+            ..SYMBOL_LAST
+        },
+    ),
+    (
+        "zh",
+        CurrencyData {
+            symbols: &[("CNY", "¥"), ("USD", "US$"), ("JPY", "JP¥")],
+            ..SYMBOL_FIRST
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(tag: &str, amount: impl Into<Number>, currency: &str) -> String {
+        format_currency(tag, Money::new(amount, currency).unwrap())
+    }
+
+    #[test]
+    fn test_symbol_placement() {
+        assert_eq!(format("en", 1234.5, "USD"), "$1,234.50");
+        assert_eq!(format("de", 1234.5, "EUR"), "1.234,50\u{a0}€");
+        assert_eq!(format("pt", 1234.5, "BRL"), "R$\u{a0}1.234,50");
+        assert_eq!(format("da", 20, "DKK"), "20,00\u{a0}kr.");
+        assert_eq!(format("zh", 8, "CNY"), "¥8.00");
+        assert_eq!(format("hi", 1234567, "INR"), "₹12,34,567.00");
+        assert_eq!(format("ar", 5, "USD"), "\u{200f}٥٫٠٠\u{a0}US$");
+    }
+
+    #[test]
+    fn test_minor_units() {
+        assert_eq!(format("en", 1234.56, "JPY"), "¥1,235");
+        assert_eq!(format("en", 1.2346, "KWD"), "KWD\u{a0}1.235");
+        assert_eq!(format("ko", 5000, "KRW"), "₩5,000");
+    }
+
+    #[test]
+    fn test_negative_amounts() {
+        assert_eq!(format("en", -3.5, "GBP"), "-£3.50");
+        assert_eq!(format("fr", -3.5, "EUR"), "-3,50\u{a0}€");
+        assert_eq!(format("en", -0.001, "EUR"), "€0.00");
+    }
+
+    #[test]
+    fn test_unknown_currency_uses_code() {
+        assert_eq!(format("en", 10, "CHF"), "CHF\u{a0}10.00");
+        assert_eq!(format("de", 10, "chf"), "10,00\u{a0}CHF");
+    }
+}
@@ -0,0 +1,1045 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{lookup, number::native_digits};
+
+/// A value for a `{name, date}`, `{name, time}` or `{name, datetime}` placeholder.
+///
+/// Converted from [`SystemTime`] (in UTC) or, with the `chrono` feature, from chrono's date and
+/// time types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    /// Offset from UTC in seconds, `None` for a local time without zone
+    offset: Option<i32>,
+}
+
+impl DateTime {
+    /// A date and time without a time zone, `None` if any field is out of range.
+    pub fn new(year: i32, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        let valid = (1..=12).contains(&month)
+            && day >= 1
+            && day <= days_in_month(year, month)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+
+        valid.then_some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            offset: None,
+        })
+    }
+
+    /// Sets the offset from UTC in seconds, shown by the `long` and `full` time formats.
+    pub fn with_offset(self, offset_seconds: i32) -> Self {
+        Self {
+            offset: Some(offset_seconds),
+            ..self
+        }
+    }
+
+    /// Builds the date and time `seconds` after the Unix epoch, in UTC.
+    pub fn from_unix_timestamp(seconds: i64) -> Self {
+        let days = seconds.div_euclid(86_400);
+        let seconds_of_day = seconds.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day % 3600 / 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+            offset: Some(0),
+        }
+    }
+
+    /// 0 for Sunday up to 6 for Saturday.
+    fn weekday(&self) -> usize {
+        let days = days_from_civil(self.year, self.month, self.day);
+        // 1970-01-01 was a Thursday
+        (days + 4).rem_euclid(7) as usize
+    }
+}
+
+impl From<SystemTime> for DateTime {
+    fn from(time: SystemTime) -> Self {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs_f64().ceil() as i64),
+        };
+        DateTime::from_unix_timestamp(seconds)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+    fn from(time: chrono::DateTime<Tz>) -> Self {
+        use chrono::Offset;
+
+        let offset = time.offset().fix().local_minus_utc();
+        DateTime::from(time.naive_local()).with_offset(offset)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for DateTime {
+    fn from(time: chrono::NaiveDateTime) -> Self {
+        use chrono::{Datelike, Timelike};
+
+        Self {
+            year: time.year(),
+            month: time.month() as u8,
+            day: time.day() as u8,
+            hour: time.hour() as u8,
+            minute: time.minute() as u8,
+            second: time.second().min(59) as u8,
+            offset: None,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for DateTime {
+    fn from(date: chrono::NaiveDate) -> Self {
+        DateTime::from(date.and_time(chrono::NaiveTime::MIN))
+    }
+}
+
+/// Which parts of a [`DateTime`] a placeholder shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeFields {
+    /// `{when, date}`
+    #[default]
+    Date,
+    /// `{when, time}`
+    Time,
+    /// `{when, datetime}`
+    DateTime,
+}
+
+/// The CLDR format length, given after the fields: `{when, date, long}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeLength {
+    Short,
+    #[default]
+    Medium,
+    Long,
+    Full,
+}
+
+/// Options of a `{name, date|time|datetime, length}` placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DateTimeOptions {
+    pub fields: DateTimeFields,
+    pub length: DateTimeLength,
+}
+
+/// Formats `value` with the CLDR date and time patterns of the language `tag`.
+///
+/// ```
+/// use i18nify::format::{format_date_time, DateTime, DateTimeFields, DateTimeLength, DateTimeOptions};
+///
+/// let when = DateTime::new(2024, 3, 5, 14, 30, 0).unwrap();
+/// let options = DateTimeOptions { fields: DateTimeFields::DateTime, length: DateTimeLength::Medium };
+///
+/// assert_eq!(format_date_time("en", when, &options), "Mar 5, 2024, 2:30:00\u{202f}PM");
+/// assert_eq!(format_date_time("de", when, &options), "05.03.2024, 14:30:00");
+/// ```
+pub fn format_date_time(tag: &str, value: DateTime, options: &DateTimeOptions) -> String {
+    let data = lookup(DATE_DATA, tag);
+    let index = match options.length {
+        DateTimeLength::Full => 0,
+        DateTimeLength::Long => 1,
+        DateTimeLength::Medium => 2,
+        DateTimeLength::Short => 3,
+    };
+
+    let formatted = match options.fields {
+        DateTimeFields::Date => format_pattern(data.date[index], &value, data),
+        DateTimeFields::Time => format_pattern(data.time[index], &value, data),
+        DateTimeFields::DateTime => {
+            let date = format_pattern(data.date[index], &value, data);
+            let time = format_pattern(data.time[index], &value, data);
+            // The glue pattern only contains quoted literals besides {0} and {1}
+            format_pattern(data.date_time[index], &value, data)
+                .replace("{1}", &date)
+                .replace("{0}", &time)
+        }
+    };
+
+    match native_digits(tag) {
+        Some(native) => formatted
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => native[digit as usize],
+                None => c,
+            })
+            .collect(),
+        None => formatted,
+    }
+}
+
+/// Interprets the subset of the CLDR date field symbols used by the compiled in patterns.
+fn format_pattern(pattern: &str, value: &DateTime, data: &DateData) -> String {
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let c = chars[idx];
+
+        if c == '\'' {
+            // 'literal', with '' being a single quote
+            if chars.get(idx + 1) == Some(&'\'') {
+                out.push('\'');
+                idx += 2;
+                continue;
+            }
+            idx += 1;
+            while idx < chars.len() && chars[idx] != '\'' {
+                out.push(chars[idx]);
+                idx += 1;
+            }
+            idx += 1;
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            out.push(c);
+            idx += 1;
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.get(idx + count) == Some(&c) {
+            count += 1;
+        }
+        idx += count;
+
+        let month = usize::from(value.month - 1);
+        let hour12 = match value.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        match (c, count) {
+            ('y', 2) => out.push_str(&format!("{:02}", value.year.rem_euclid(100))),
+            ('y', _) => out.push_str(&value.year.to_string()),
+            ('M' | 'L', 1) => out.push_str(&value.month.to_string()),
+            ('M' | 'L', 2) => out.push_str(&format!("{:02}", value.month)),
+            ('M' | 'L', 3) => out.push_str(data.months_abbreviated[month]),
+            ('M' | 'L', _) => out.push_str(data.months[month]),
+            ('d', 1) => out.push_str(&value.day.to_string()),
+            ('d', _) => out.push_str(&format!("{:02}", value.day)),
+            ('E', 4) => out.push_str(data.weekdays[value.weekday()]),
+            ('E', _) => out.push_str(data.weekdays_abbreviated[value.weekday()]),
+            ('h', 1) => out.push_str(&hour12.to_string()),
+            ('h', _) => out.push_str(&format!("{:02}", hour12)),
+            ('H', 1) => out.push_str(&value.hour.to_string()),
+            ('H', _) => out.push_str(&format!("{:02}", value.hour)),
+            ('m', 1) => out.push_str(&value.minute.to_string()),
+            ('m', _) => out.push_str(&format!("{:02}", value.minute)),
+            ('s', 1) => out.push_str(&value.second.to_string()),
+            ('s', _) => out.push_str(&format!("{:02}", value.second)),
+            ('a', _) => out.push_str(data.day_periods[usize::from(value.hour >= 12)]),
+            ('z', count) => {
+                if let Some(offset) = value.offset {
+                    out.push_str(&zone(offset, count == 4));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A local time has no zone, nor the parentheses around it, as in `H:mm:ss (zzzz)`
+    if value.offset.is_none() {
+        out = out.replace("()", "");
+    }
+    out.trim().to_string()
+}
+
+/// `UTC` or a GMT offset such as `GMT+2` (short) and `GMT+02:00` (long).
+fn zone(offset: i32, long: bool) -> String {
+    if offset == 0 {
+        return "UTC".to_string();
+    }
+
+    let sign = if offset < 0 { '-' } else { '+' };
+    let hours = offset.unsigned_abs() / 3600;
+    let minutes = offset.unsigned_abs() % 3600 / 60;
+    if long {
+        format!("GMT{}{:02}:{:02}", sign, hours, minutes)
+    } else if minutes == 0 {
+        format!("GMT{}{}", sign, hours)
+    } else {
+        format!("GMT{}{}:{:02}", sign, hours, minutes)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+struct DateData {
+    /// full, long, medium and short patterns
+    date: [&'static str; 4],
+    time: [&'static str; 4],
+    /// How date and time are combined, `{1}` is the date and `{0}` the time
+    date_time: [&'static str; 4],
+    months: [&'static str; 12],
+    months_abbreviated: [&'static str; 12],
+    /// Starting with Sunday
+    weekdays: [&'static str; 7],
+    weekdays_abbreviated: [&'static str; 7],
+    /// AM and PM
+    day_periods: [&'static str; 2],
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const EN_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const EN_WEEKDAYS_ABBREVIATED: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const TIME_24H: [&str; 4] = ["HH:mm:ss zzzz", "HH:mm:ss z", "HH:mm:ss", "HH:mm"];
+
+const NUMERIC_MONTHS: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+static DATE_DATA: &[(&str, DateData)] = &[
+    (
+        "en",
+        DateData {
+            date: ["EEEE, MMMM d, y", "MMMM d, y", "MMM d, y", "M/d/yy"],
+            time: [
+                "h:mm:ss\u{202f}a zzzz",
+                "h:mm:ss\u{202f}a z",
+                "h:mm:ss\u{202f}a",
+                "h:mm\u{202f}a",
+            ],
+            date_time: ["{1} 'at' {0}", "{1} 'at' {0}", "{1}, {0}", "{1}, {0}"],
+            months: EN_MONTHS,
+            months_abbreviated: [
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+            ],
+            weekdays: EN_WEEKDAYS,
+            weekdays_abbreviated: EN_WEEKDAYS_ABBREVIATED,
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "en-GB",
+        DateData {
+            date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+            time: TIME_24H,
+            date_time: ["{1} 'at' {0}", "{1} 'at' {0}", "{1}, {0}", "{1}, {0}"],
+            months: EN_MONTHS,
+            months_abbreviated: [
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sept", "Oct", "Nov", "Dec",
+            ],
+            weekdays: EN_WEEKDAYS,
+            weekdays_abbreviated: EN_WEEKDAYS_ABBREVIATED,
+            day_periods: ["am", "pm"],
+        },
+    ),
+    (
+        "ar",
+        DateData {
+            date: [
+                "EEEE، d MMMM y",
+                "d MMMM y",
+                "dd\u{200f}/MM\u{200f}/y",
+                "d\u{200f}/M\u{200f}/y",
+            ],
+            time: ["h:mm:ss a zzzz", "h:mm:ss a z", "h:mm:ss a", "h:mm a"],
+            date_time: ["{1} في {0}", "{1} في {0}", "{1}، {0}", "{1}، {0}"],
+            months: [
+                "يناير",
+                "فبراير",
+                "مارس",
+                "أبريل",
+                "مايو",
+                "يونيو",
+                "يوليو",
+                "أغسطس",
+                "سبتمبر",
+                "أكتوبر",
+                "نوفمبر",
+                "ديسمبر",
+            ],
+            months_abbreviated: [
+                "يناير",
+                "فبراير",
+                "مارس",
+                "أبريل",
+                "مايو",
+                "يونيو",
+                "يوليو",
+                "أغسطس",
+                "سبتمبر",
+                "أكتوبر",
+                "نوفمبر",
+                "ديسمبر",
+            ],
+            weekdays: [
+                "الأحد",
+                "الاثنين",
+                "الثلاثاء",
+                "الأربعاء",
+                "الخميس",
+                "الجمعة",
+                "السبت",
+            ],
+            weekdays_abbreviated: [
+                "الأحد",
+                "الاثنين",
+                "الثلاثاء",
+                "الأربعاء",
+                "الخميس",
+                "الجمعة",
+                "السبت",
+            ],
+            day_periods: ["ص", "م"],
+        },
+    ),
+    (
+        "da",
+        DateData {
+            date: ["EEEE 'den' d. MMMM y", "d. MMMM y", "d. MMM y", "dd.MM.y"],
+            time: ["HH.mm.ss zzzz", "HH.mm.ss z", "HH.mm.ss", "HH.mm"],
+            date_time: ["{1} 'kl'. {0}", "{1} 'kl'. {0}", "{1} {0}", "{1} {0}"],
+            months: [
+                "januar",
+                "februar",
+                "marts",
+                "april",
+                "maj",
+                "juni",
+                "juli",
+                "august",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            months_abbreviated: [
+                "jan.", "feb.", "mar.", "apr.", "maj", "jun.", "jul.", "aug.", "sep.", "okt.",
+                "nov.", "dec.",
+            ],
+            weekdays: [
+                "søndag", "mandag", "tirsdag", "onsdag", "torsdag", "fredag", "lørdag",
+            ],
+            weekdays_abbreviated: ["søn.", "man.", "tirs.", "ons.", "tors.", "fre.", "lør."],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "de",
+        DateData {
+            date: ["EEEE, d. MMMM y", "d. MMMM y", "dd.MM.y", "dd.MM.yy"],
+            time: TIME_24H,
+            date_time: ["{1} 'um' {0}", "{1} 'um' {0}", "{1}, {0}", "{1}, {0}"],
+            months: [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            months_abbreviated: [
+                "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.",
+                "Nov.", "Dez.",
+            ],
+            weekdays: [
+                "Sonntag",
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+            ],
+            weekdays_abbreviated: ["So.", "Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa."],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "es",
+        DateData {
+            date: [
+                "EEEE, d 'de' MMMM 'de' y",
+                "d 'de' MMMM 'de' y",
+                "d MMM y",
+                "d/M/yy",
+            ],
+            time: ["H:mm:ss (zzzz)", "H:mm:ss z", "H:mm:ss", "H:mm"],
+            date_time: ["{1}, {0}", "{1}, {0}", "{1}, {0}", "{1}, {0}"],
+            months: [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            months_abbreviated: [
+                "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+            ],
+            weekdays: [
+                "domingo",
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+            ],
+            weekdays_abbreviated: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+            day_periods: ["a.\u{a0}m.", "p.\u{a0}m."],
+        },
+    ),
+    (
+        "fr",
+        DateData {
+            date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+            time: TIME_24H,
+            date_time: ["{1} 'à' {0}", "{1} 'à' {0}", "{1}, {0}", "{1} {0}"],
+            months: [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            months_abbreviated: [
+                "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+                "nov.", "déc.",
+            ],
+            weekdays: [
+                "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+            ],
+            weekdays_abbreviated: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "hi",
+        DateData {
+            date: ["EEEE, d MMMM y", "d MMMM y", "d MMM y", "d/M/yy"],
+            time: ["h:mm:ss a zzzz", "h:mm:ss a z", "h:mm:ss a", "h:mm a"],
+            date_time: ["{1} को {0}", "{1} को {0}", "{1}, {0}", "{1}, {0}"],
+            months: [
+                "जनवरी",
+                "फ़रवरी",
+                "मार्च",
+                "अप्रैल",
+                "मई",
+                "जून",
+                "जुलाई",
+                "अगस्त",
+                "सितंबर",
+                "अक्तूबर",
+                "नवंबर",
+                "दिसंबर",
+            ],
+            months_abbreviated: [
+                "जन॰",
+                "फ़र॰",
+                "मार्च",
+                "अप्रैल",
+                "मई",
+                "जून",
+                "जुल॰",
+                "अग॰",
+                "सित॰",
+                "अक्तू॰",
+                "नव॰",
+                "दिस॰",
+            ],
+            weekdays: [
+                "रविवार",
+                "सोमवार",
+                "मंगलवार",
+                "बुधवार",
+                "गुरुवार",
+                "शुक्रवार",
+                "शनिवार",
+            ],
+            weekdays_abbreviated: ["रवि", "सोम", "मंगल", "बुध", "गुरु", "शुक्र", "शनि"],
+            day_periods: ["am", "pm"],
+        },
+    ),
+    (
+        "it",
+        DateData {
+            date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/yy"],
+            time: TIME_24H,
+            date_time: ["{1} {0}", "{1} {0}", "{1}, {0}", "{1}, {0}"],
+            months: [
+                "gennaio",
+                "febbraio",
+                "marzo",
+                "aprile",
+                "maggio",
+                "giugno",
+                "luglio",
+                "agosto",
+                "settembre",
+                "ottobre",
+                "novembre",
+                "dicembre",
+            ],
+            months_abbreviated: [
+                "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+            ],
+            weekdays: [
+                "domenica",
+                "lunedì",
+                "martedì",
+                "mercoledì",
+                "giovedì",
+                "venerdì",
+                "sabato",
+            ],
+            weekdays_abbreviated: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "ja",
+        DateData {
+            date: ["y年M月d日EEEE", "y年M月d日", "y/MM/dd", "y/MM/dd"],
+            time: ["H時mm分ss秒 zzzz", "H:mm:ss z", "H:mm:ss", "H:mm"],
+            date_time: ["{1} {0}", "{1} {0}", "{1} {0}", "{1} {0}"],
+            months: NUMERIC_MONTHS,
+            months_abbreviated: NUMERIC_MONTHS,
+            weekdays: [
+                "日曜日",
+                "月曜日",
+                "火曜日",
+                "水曜日",
+                "木曜日",
+                "金曜日",
+                "土曜日",
+            ],
+            weekdays_abbreviated: ["日", "月", "火", "水", "木", "金", "土"],
+            day_periods: ["午前", "午後"],
+        },
+    ),
+    (
+        "ko",
+        DateData {
+            date: ["y년 MMMM d일 EEEE", "y년 MMMM d일", "y. M. d.", "yy. M. d."],
+            time: [
+                "a h시 m분 s초 zzzz",
+                "a h시 m분 s초 z",
+                "a h:mm:ss",
+                "a h:mm",
+            ],
+            date_time: ["{1} {0}", "{1} {0}", "{1} {0}", "{1} {0}"],
+            months: [
+                "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월",
+                "12월",
+            ],
+            months_abbreviated: [
+                "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월",
+                "12월",
+            ],
+            weekdays: [
+                "일요일",
+                "월요일",
+                "화요일",
+                "수요일",
+                "목요일",
+                "금요일",
+                "토요일",
+            ],
+            weekdays_abbreviated: ["일", "월", "화", "수", "목", "금", "토"],
+            day_periods: ["오전", "오후"],
+        },
+    ),
+    (
+        "nl",
+        DateData {
+            date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd-MM-y"],
+            time: TIME_24H,
+            date_time: ["{1} 'om' {0}", "{1} 'om' {0}", "{1} {0}", "{1} {0}"],
+            months: [
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            months_abbreviated: [
+                "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+            ],
+            weekdays: [
+                "zondag",
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+            ],
+            weekdays_abbreviated: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+            day_periods: ["a.m.", "p.m."],
+        },
+    ),
+    (
+        "pt",
+        DateData {
+            date: [
+                "EEEE, d 'de' MMMM 'de' y",
+                "d 'de' MMMM 'de' y",
+                "d 'de' MMM 'de' y",
+                "dd/MM/y",
+            ],
+            time: TIME_24H,
+            date_time: ["{1} {0}", "{1} {0}", "{1} {0}", "{1} {0}"],
+            months: [
+                "janeiro",
+                "fevereiro",
+                "março",
+                "abril",
+                "maio",
+                "junho",
+                "julho",
+                "agosto",
+                "setembro",
+                "outubro",
+                "novembro",
+                "dezembro",
+            ],
+            months_abbreviated: [
+                "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.",
+                "nov.", "dez.",
+            ],
+            weekdays: [
+                "domingo",
+                "segunda-feira",
+                "terça-feira",
+                "quarta-feira",
+                "quinta-feira",
+                "sexta-feira",
+                "sábado",
+            ],
+            weekdays_abbreviated: ["dom.", "seg.", "ter.", "qua.", "qui.", "sex.", "sáb."],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "ru",
+        DateData {
+            date: [
+                "EEEE, d MMMM y 'г'.",
+                "d MMMM y 'г'.",
+                "d MMM y 'г'.",
+                "dd.MM.y",
+            ],
+            time: TIME_24H,
+            date_time: ["{1}, {0}", "{1}, {0}", "{1}, {0}", "{1}, {0}"],
+            months: [
+                "января",
+                "февраля",
+                "марта",
+                "апреля",
+                "мая",
+                "июня",
+                "июля",
+                "августа",
+                "сентября",
+                "октября",
+                "ноября",
+                "декабря",
+            ],
+            months_abbreviated: [
+                "янв.",
+                "февр.",
+                "мар.",
+                "апр.",
+                "мая",
+                "июн.",
+                "июл.",
+                "авг.",
+                "сент.",
+                "окт.",
+                "нояб.",
+                "дек.",
+            ],
+            weekdays: [
+                "воскресенье",
+                "понедельник",
+                "вторник",
+                "среда",
+                "четверг",
+                "пятница",
+                "суббота",
+            ],
+            weekdays_abbreviated: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+            day_periods: ["AM", "PM"],
+        },
+    ),
+    (
+        "zh",
+        DateData {
+            date: ["y年M月d日EEEE", "y年M月d日", "y年M月d日", "y/M/d"],
+            time: ["zzzz HH:mm:ss", "z HH:mm:ss", "HH:mm:ss", "HH:mm"],
+            date_time: ["{1} {0}", "{1} {0}", "{1} {0}", "{1} {0}"],
+            months: [
+                "一月",
+                "二月",
+                "三月",
+                "四月",
+                "五月",
+                "六月",
+                "七月",
+                "八月",
+                "九月",
+                "十月",
+                "十一月",
+                "十二月",
+            ],
+            months_abbreviated: NUMERIC_MONTHS,
+            weekdays: [
+                "星期日",
+                "星期一",
+                "星期二",
+                "星期三",
+                "星期四",
+                "星期五",
+                "星期六",
+            ],
+            weekdays_abbreviated: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+            day_periods: ["上午", "下午"],
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(
+        tag: &str,
+        value: DateTime,
+        fields: DateTimeFields,
+        length: DateTimeLength,
+    ) -> String {
+        format_date_time(tag, value, &DateTimeOptions { fields, length })
+    }
+
+    fn when() -> DateTime {
+        DateTime::new(2024, 3, 5, 14, 30, 5).unwrap()
+    }
+
+    #[test]
+    fn test_date_lengths() {
+        use DateTimeFields::Date;
+
+        assert_eq!(
+            format("en", when(), Date, DateTimeLength::Full),
+            "Tuesday, March 5, 2024"
+        );
+        assert_eq!(format("en", when(), Date, DateTimeLength::Short), "3/5/24");
+        assert_eq!(
+            format("en-GB", when(), Date, DateTimeLength::Medium),
+            "5 Mar 2024"
+        );
+        assert_eq!(
+            format("es", when(), Date, DateTimeLength::Long),
+            "5 de marzo de 2024"
+        );
+        assert_eq!(
+            format("ru", when(), Date, DateTimeLength::Long),
+            "5 марта 2024 г."
+        );
+        assert_eq!(
+            format("ja", when(), Date, DateTimeLength::Full),
+            "2024年3月5日火曜日"
+        );
+        assert_eq!(
+            format("ar", when(), Date, DateTimeLength::Long),
+            "٥ مارس ٢٠٢٤"
+        );
+    }
+
+    #[test]
+    fn test_time_hour_cycles() {
+        use DateTimeFields::Time;
+
+        assert_eq!(
+            format("en", when(), Time, DateTimeLength::Short),
+            "2:30\u{202f}PM"
+        );
+        assert_eq!(format("de", when(), Time, DateTimeLength::Short), "14:30");
+        assert_eq!(
+            format("da", when(), Time, DateTimeLength::Medium),
+            "14.30.05"
+        );
+        assert_eq!(
+            format("ko", when(), Time, DateTimeLength::Short),
+            "오후 2:30"
+        );
+        assert_eq!(
+            format("en", when().with_offset(7200), Time, DateTimeLength::Long),
+            "2:30:05\u{202f}PM GMT+2"
+        );
+        assert_eq!(
+            format("fr", when().with_offset(-16200), Time, DateTimeLength::Full),
+            "14:30:05 GMT-04:30"
+        );
+    }
+
+    #[test]
+    fn test_date_time() {
+        assert_eq!(
+            format(
+                "en",
+                when().with_offset(0),
+                DateTimeFields::DateTime,
+                DateTimeLength::Long
+            ),
+            "March 5, 2024 at 2:30:05\u{202f}PM UTC"
+        );
+        assert_eq!(
+            format(
+                "de",
+                when().with_offset(0),
+                DateTimeFields::DateTime,
+                DateTimeLength::Full
+            ),
+            "Dienstag, 5. März 2024 um 14:30:05 UTC"
+        );
+    }
+
+    #[test]
+    fn test_local_time_has_no_zone() {
+        assert_eq!(
+            format("en", when(), DateTimeFields::DateTime, DateTimeLength::Long),
+            "March 5, 2024 at 2:30:05\u{202f}PM"
+        );
+        assert_eq!(
+            format("es", when(), DateTimeFields::Time, DateTimeLength::Full),
+            "14:30:05"
+        );
+        assert_eq!(
+            format("zh", when(), DateTimeFields::Time, DateTimeLength::Full),
+            "14:30:05"
+        );
+    }
+
+    #[test]
+    fn test_from_unix_timestamp() {
+        assert_eq!(
+            DateTime::from_unix_timestamp(0),
+            DateTime::new(1970, 1, 1, 0, 0, 0).unwrap().with_offset(0)
+        );
+        assert_eq!(
+            DateTime::from_unix_timestamp(1_709_649_005),
+            when().with_offset(0)
+        );
+        assert_eq!(
+            DateTime::from_unix_timestamp(-1),
+            DateTime::new(1969, 12, 31, 23, 59, 59)
+                .unwrap()
+                .with_offset(0)
+        );
+        assert_eq!(DateTime::new(2023, 2, 29, 0, 0, 0), None);
+    }
+}
@@ -0,0 +1,314 @@
+use super::lookup;
+
+/// How the items of a `{name, list, ...}` placeholder are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListStyle {
+    /// `{names, list}` or `{names, list, conjunction}`: "Alice, Bob, and Carol".
+    #[default]
+    Conjunction,
+    /// `{names, list, disjunction}`: "Alice, Bob, or Carol".
+    Disjunction,
+    /// `{sizes, list, unit}`: "3 ft, 7 in".
+    Unit,
+}
+
+/// Joins `items` with the CLDR list patterns of the language `tag`.
+///
+/// ```
+/// use i18nify::format::{format_list, ListStyle};
+///
+/// let names = ["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+///
+/// assert_eq!(format_list("en", &names, ListStyle::Conjunction), "Alice, Bob, and Carol");
+/// assert_eq!(format_list("de", &names, ListStyle::Conjunction), "Alice, Bob und Carol");
+/// assert_eq!(format_list("zh", &names, ListStyle::Conjunction), "Alice、Bob和Carol");
+/// assert_eq!(format_list("en", &names[..2], ListStyle::Disjunction), "Alice or Bob");
+/// ```
+pub fn format_list(tag: &str, items: &[String], style: ListStyle) -> String {
+    let data = lookup(LIST_DATA, tag);
+    let patterns = match style {
+        ListStyle::Conjunction => &data.conjunction,
+        ListStyle::Disjunction => &data.disjunction,
+        ListStyle::Unit => &data.unit,
+    };
+    let spanish = tag
+        .split(['-', '_'])
+        .next()
+        .is_some_and(|language| language.eq_ignore_ascii_case("es"));
+    let join = |pattern: &str, first: &str, second: &str| {
+        let pattern = if spanish {
+            spanish_conjunction(pattern, second)
+        } else {
+            pattern.to_string()
+        };
+        apply(&pattern, first, second)
+    };
+
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [first, second] => join(patterns.two, first, second),
+        [first, middle @ .., last] => {
+            let (before_last, middle) = middle.split_last().unwrap_or((first, &[]));
+            let mut out = join(patterns.end, before_last, last);
+            for item in middle.iter().rev() {
+                out = join(patterns.middle, item, &out);
+            }
+            join(patterns.start, first, &out)
+        }
+    }
+}
+
+/// Substitutes `{0}` and `{1}` once, so braces in the items are left alone.
+fn apply(pattern: &str, first: &str, second: &str) -> String {
+    let (before, rest) = pattern.split_once("{0}").unwrap_or(("", pattern));
+    let (between, after) = rest.split_once("{1}").unwrap_or((rest, ""));
+    format!("{}{}{}{}{}", before, first, between, second, after)
+}
+
+/// Spanish writes "e" instead of "y" before an /i/ sound and "u" instead of "o" before an /o/
+/// sound: "Fernando e Isabel", "siete u ocho".
+fn spanish_conjunction(pattern: &str, next: &str) -> String {
+    let next = next.to_lowercase();
+    let starts_with_i = (next.starts_with('i') || next.starts_with("hi"))
+        && !["hia", "hie", "hio", "hiu"]
+            .iter()
+            .any(|prefix| next.starts_with(prefix));
+    let starts_with_o = next.starts_with('o') || next.starts_with("ho");
+
+    if starts_with_i {
+        pattern.replace(" y ", " e ")
+    } else if starts_with_o {
+        pattern.replace(" o ", " u ")
+    } else {
+        pattern.to_string()
+    }
+}
+
+struct ListPatterns {
+    /// Exactly two items
+    two: &'static str,
+    /// The first two of three or more items
+    start: &'static str,
+    middle: &'static str,
+    /// The last two of three or more items
+    end: &'static str,
+}
+
+const fn joined(two: &'static str, end: &'static str) -> ListPatterns {
+    ListPatterns {
+        two,
+        start: "{0}, {1}",
+        middle: "{0}, {1}",
+        end,
+    }
+}
+
+const fn uniform(pattern: &'static str) -> ListPatterns {
+    ListPatterns {
+        two: pattern,
+        start: pattern,
+        middle: pattern,
+        end: pattern,
+    }
+}
+
+struct ListData {
+    conjunction: ListPatterns,
+    disjunction: ListPatterns,
+    unit: ListPatterns,
+}
+
+static LIST_DATA: &[(&str, ListData)] = &[
+    (
+        "en",
+        ListData {
+            conjunction: joined("{0} and {1}", "{0}, and {1}"),
+            disjunction: joined("{0} or {1}", "{0}, or {1}"),
+            unit: uniform("{0}, {1}"),
+        },
+    ),
+    (
+        "en-GB",
+        ListData {
+            conjunction: joined("{0} and {1}", "{0} and {1}"),
+            disjunction: joined("{0} or {1}", "{0} or {1}"),
+            unit: uniform("{0}, {1}"),
+        },
+    ),
+    (
+        "ar",
+        ListData {
+            conjunction: uniform("{0} و{1}"),
+            disjunction: uniform("{0} أو {1}"),
+            unit: uniform("{0} و{1}"),
+        },
+    ),
+    (
+        "da",
+        ListData {
+            conjunction: joined("{0} og {1}", "{0} og {1}"),
+            disjunction: joined("{0} eller {1}", "{0} eller {1}"),
+            unit: joined("{0} og {1}", "{0} og {1}"),
+        },
+    ),
+    (
+        "de",
+        ListData {
+            conjunction: joined("{0} und {1}", "{0} und {1}"),
+            disjunction: joined("{0} oder {1}", "{0} oder {1}"),
+            unit: joined("{0}, {1}", "{0} und {1}"),
+        },
+    ),
+    (
+        "es",
+        ListData {
+            conjunction: joined("{0} y {1}", "{0} y {1}"),
+            disjunction: joined("{0} o {1}", "{0} o {1}"),
+            unit: joined("{0} y {1}", "{0} y {1}"),
+        },
+    ),
+    (
+        "fr",
+        ListData {
+            conjunction: joined("{0} et {1}", "{0} et {1}"),
+            disjunction: joined("{0} ou {1}", "{0} ou {1}"),
+            unit: joined("{0} et {1}", "{0} et {1}"),
+        },
+    ),
+    (
+        "hi",
+        ListData {
+            conjunction: joined("{0} और {1}", "{0}, और {1}"),
+            disjunction: joined("{0} या {1}", "{0} या {1}"),
+            unit: joined("{0}, {1}", "{0}, और {1}"),
+        },
+    ),
+    (
+        "it",
+        ListData {
+            conjunction: joined("{0} e {1}", "{0} e {1}"),
+            disjunction: joined("{0} o {1}", "{0} o {1}"),
+            unit: joined("{0} e {1}", "{0} e {1}"),
+        },
+    ),
+    (
+        "ja",
+        ListData {
+            conjunction: uniform("{0}、{1}"),
+            disjunction: uniform("{0}または{1}"),
+            unit: uniform("{0} {1}"),
+        },
+    ),
+    (
+        "ko",
+        ListData {
+            conjunction: joined("{0} 및 {1}", "{0} 및 {1}"),
+            disjunction: joined("{0} 또는 {1}", "{0} 또는 {1}"),
+            unit: uniform("{0} {1}"),
+        },
+    ),
+    (
+        "nl",
+        ListData {
+            conjunction: joined("{0} en {1}", "{0} en {1}"),
+            disjunction: joined("{0} of {1}", "{0} of {1}"),
+            unit: joined("{0} en {1}", "{0} en {1}"),
+        },
+    ),
+    (
+        "pt",
+        ListData {
+            conjunction: joined("{0} e {1}", "{0} e {1}"),
+            disjunction: joined("{0} ou {1}", "{0} ou {1}"),
+            unit: joined("{0} e {1}", "{0} e {1}"),
+        },
+    ),
+    (
+        "ru",
+        ListData {
+            conjunction: joined("{0} и {1}", "{0} и {1}"),
+            disjunction: joined("{0} или {1}", "{0} или {1}"),
+            unit: uniform("{0} {1}"),
+        },
+    ),
+    (
+        "zh",
+        ListData {
+            conjunction: ListPatterns {
+                two: "{0}和{1}",
+                start: "{0}、{1}",
+                middle: "{0}、{1}",
+                end: "{0}和{1}",
+            },
+            disjunction: ListPatterns {
+                two: "{0}或{1}",
+                start: "{0}、{1}",
+                middle: "{0}、{1}",
+                end: "{0}或{1}",
+            },
+            unit: uniform("{0}{1}"),
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(tag: &str, items: &[&str], style: ListStyle) -> String {
+        let items = items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>();
+        format_list(tag, &items, style)
+    }
+
+    #[test]
+    fn test_list_lengths() {
+        assert_eq!(format("en", &[], ListStyle::Conjunction), "");
+        assert_eq!(format("en", &["a"], ListStyle::Conjunction), "a");
+        assert_eq!(format("en", &["a", "b"], ListStyle::Conjunction), "a and b");
+        assert_eq!(
+            format("en", &["a", "b", "c", "d"], ListStyle::Conjunction),
+            "a, b, c, and d"
+        );
+        assert_eq!(
+            format("en-GB", &["a", "b", "c"], ListStyle::Conjunction),
+            "a, b and c"
+        );
+    }
+
+    #[test]
+    fn test_list_styles() {
+        assert_eq!(
+            format("fr", &["a", "b", "c"], ListStyle::Disjunction),
+            "a, b ou c"
+        );
+        assert_eq!(
+            format("ja", &["a", "b", "c"], ListStyle::Conjunction),
+            "a、b、c"
+        );
+        assert_eq!(
+            format("ru", &["1 м", "20 см"], ListStyle::Unit),
+            "1 м 20 см"
+        );
+        assert_eq!(format("en", &["{0}", "{1}"], ListStyle::Unit), "{0}, {1}");
+    }
+
+    #[test]
+    fn test_spanish_conjunctions() {
+        assert_eq!(
+            format("es", &["Fernando", "Isabel"], ListStyle::Conjunction),
+            "Fernando e Isabel"
+        );
+        assert_eq!(
+            format("es", &["agua", "hielo"], ListStyle::Conjunction),
+            "agua y hielo"
+        );
+        assert_eq!(
+            format("es", &["siete", "ocho"], ListStyle::Disjunction),
+            "siete u ocho"
+        );
+    }
+}
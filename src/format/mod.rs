@@ -0,0 +1,106 @@
+//! Locale aware formatting of typed placeholders.
+//!
+//! A placeholder can name a format after its name, the derive then generates a typed argument
+//! and formats it with the conventions of each locale:
+//!
+//! ```json
+//! {
+//!     "balance": "{name}, your balance is {amount, number, min_fraction=2}",
+//!     "progress": "{done, number, percent} done",
+//!     "followers": "{count, number, compact} followers",
+//!     "due": "Due {day, date, long} at {time, time, short}",
+//!     "total": "Total: {price, currency}",
+//!     "last_seen": "Last seen {delta, relative_time}",
+//!     "uptime": "Up for {elapsed, duration}",
+//!     "liked_by": "Liked by {names, list}"
+//! }
+//! ```
+//!
+//! Dates and times take a [`DateTime`] and are written `{name, date}`, `{name, time}` or
+//! `{name, datetime}`, optionally followed by a length: `short`, `medium` (the default), `long` or
+//! `full`. A [`SystemTime`](std::time::SystemTime) is formatted in UTC, enable the `chrono`
+//! feature to pass chrono's date and time types instead.
+//!
+//! Prices take a [`Money`], an amount together with its ISO 4217 currency code. The locale decides
+//! the symbol and where it goes, the currency decides the number of minor unit digits.
+//!
+//! `{name, relative_time}` takes a [`RelativeTime`] and picks the largest whole unit, "3 minutes
+//! ago" or "in 2 days", with the plural form the locale needs. `{name, duration}` takes a
+//! [`Duration`](std::time::Duration) and writes it in short units, "2 h 5 min".
+//!
+//! `{name, list}` takes anything iterable over displayable items and joins them with the list
+//! patterns of the locale. The style `conjunction` ("and", the default), `disjunction` ("or") or
+//! `unit` can follow.
+//!
+//! The formatting data comes from [CLDR](https://cldr.unicode.org) and is compiled into the crate,
+//! so nothing is loaded at runtime. Tags of languages without data fall back to English.
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/format_locales")]
+//!     pub struct FormatLocale;
+//! }
+//! use demo::{Locale, Name};
+//!
+//! assert_eq!(
+//!     Locale::En.balance(1234567.5, Name("Ada")),
+//!     "Ada, your balance is 1,234,567.50"
+//! );
+//! assert_eq!(
+//!     Locale::De.balance(1234567.5, Name("Ada")),
+//!     "Ada, dein Kontostand beträgt 1.234.567,50"
+//! );
+//! assert_eq!(Locale::De.progress(0.5), "50\u{a0}% erledigt");
+//! assert_eq!(Locale::En.followers(12_345_u32), "12K followers");
+//!
+//! let when = i18nify::format::DateTime::new(2024, 3, 5, 17, 45, 0).unwrap();
+//! assert_eq!(Locale::En.due(when, when), "Due March 5, 2024 at 5:45\u{202f}PM");
+//! assert_eq!(Locale::De.due(when, when), "Fällig am 5. März 2024 um 17:45");
+//!
+//! let price = i18nify::format::Money::new(1234.5, "EUR").unwrap();
+//! assert_eq!(Locale::En.total(price), "Total: €1,234.50");
+//! assert_eq!(Locale::De.total(price), "Gesamt: 1.234,50\u{a0}€");
+//!
+//! let delta = i18nify::format::RelativeTime::from_seconds(-200);
+//! assert_eq!(Locale::En.last_seen(delta), "Last seen 3 minutes ago");
+//! assert_eq!(Locale::De.last_seen(delta), "Zuletzt gesehen vor 3 Minuten");
+//! let elapsed = std::time::Duration::from_secs(7500);
+//! assert_eq!(Locale::De.uptime(elapsed), "Läuft seit 2 Std. 5 Min.");
+//!
+//! let names = ["Alice", "Bob", "Carol"];
+//! assert_eq!(Locale::En.liked_by(names), "Liked by Alice, Bob, and Carol");
+//! assert_eq!(Locale::De.liked_by(names), "Gefällt Alice, Bob und Carol");
+//! ```
+
+mod currency;
+mod date;
+mod list;
+mod number;
+mod plural;
+mod relative;
+
+pub use currency::{format_currency, Money};
+pub use date::{format_date_time, DateTime, DateTimeFields, DateTimeLength, DateTimeOptions};
+pub use list::{format_list, ListStyle};
+pub use number::{format_number, Number, NumberOptions, NumberStyle};
+pub use relative::{format_duration, format_relative_time, RelativeTime};
+
+/// Finds the entry for `tag` in a table keyed by language tags.
+///
+/// Tries the full tag first, then its language subtag and finally English.
+fn lookup<T>(table: &'static [(&'static str, T)], tag: &str) -> &'static T {
+    let tag = tag.replace('_', "-");
+    let language = tag.split('-').next().unwrap_or_default();
+
+    [tag.as_str(), language, "en"]
+        .iter()
+        .find_map(|candidate| {
+            table
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(candidate))
+                .map(|(_, value)| value)
+        })
+        .unwrap_or(&table[0].1)
+}
@@ -0,0 +1,525 @@
+use super::lookup;
+
+/// A value for a `{name, number}` placeholder.
+///
+/// Created from any of the primitive integer and float types.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+macro_rules! number_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(value: $ty) -> Self {
+                    Number::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+number_from_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<f32> for Number {
+    fn from(value: f32) -> Self {
+        Number::Float(value as f64)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(value: f64) -> Self {
+        Number::Float(value)
+    }
+}
+
+impl Number {
+    fn is_negative(self) -> bool {
+        match self {
+            Number::Integer(value) => value < 0,
+            Number::Float(value) => value.is_sign_negative() && value != 0.0,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Number::Integer(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+}
+
+/// How a number is presented, chosen by the word after `number` in the placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberStyle {
+    /// `{n, number}`: grouped, up to three fraction digits.
+    #[default]
+    Decimal,
+    /// `{n, number, integer}`: rounded to a whole number.
+    Integer,
+    /// `{n, number, percent}`: `0.25` becomes `25%`.
+    Percent,
+    /// `{n, number, compact}`: `1234567` becomes `1.2M`.
+    Compact,
+}
+
+/// Options of a `{name, number, ...}` placeholder.
+///
+/// `min_fraction=N` and `max_fraction=N` override the fraction digits of the style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NumberOptions {
+    pub style: NumberStyle,
+    pub min_fraction_digits: Option<u8>,
+    pub max_fraction_digits: Option<u8>,
+}
+
+/// Formats `number` with the decimal symbols and grouping of the language `tag`.
+///
+/// ```
+/// use i18nify::format::{format_number, NumberOptions, NumberStyle};
+///
+/// let options = NumberOptions::default();
+/// assert_eq!(format_number("en", 1234567.5.into(), &options), "1,234,567.5");
+/// assert_eq!(format_number("de", 1234567.5.into(), &options), "1.234.567,5");
+/// assert_eq!(format_number("hi", 1234567.5.into(), &options), "12,34,567.5");
+///
+/// let percent = NumberOptions { style: NumberStyle::Percent, ..options };
+/// assert_eq!(format_number("fr", 0.25.into(), &percent), "25\u{202f}%");
+/// ```
+pub fn format_number(tag: &str, number: Number, options: &NumberOptions) -> String {
+    let data = lookup(NUMBER_DATA, tag);
+
+    if let Number::Float(value) = number {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        if value.is_infinite() {
+            let sign = if value < 0.0 { data.minus } else { "" };
+            return format!("{}∞", sign);
+        }
+    }
+
+    let (number, suffix, (min_fraction, max_fraction)) = match options.style {
+        NumberStyle::Decimal => (number, "", (0, 3)),
+        NumberStyle::Integer => (number, "", (0, 0)),
+        NumberStyle::Percent => {
+            let number = match number {
+                Number::Integer(value) => Number::Integer(value * 100),
+                Number::Float(value) => Number::Float(value * 100.0),
+            };
+            (number, data.percent_suffix, (0, 0))
+        }
+        NumberStyle::Compact => compact(number, data),
+    };
+
+    let min_fraction = options
+        .min_fraction_digits
+        .map(usize::from)
+        .unwrap_or(min_fraction);
+    let max_fraction = options
+        .max_fraction_digits
+        .map(usize::from)
+        .unwrap_or(max_fraction)
+        .max(min_fraction);
+
+    let (integer, fraction) = digits(number, min_fraction, max_fraction);
+
+    let mut out = String::new();
+    if number.is_negative() && integer.bytes().chain(fraction.bytes()).any(|b| b != b'0') {
+        out.push_str(data.minus);
+    }
+    out.push_str(&group(&integer, data));
+    if !fraction.is_empty() {
+        out.push_str(data.decimal);
+        out.push_str(&fraction);
+    }
+    out.push_str(suffix);
+
+    match data.digits {
+        Some(native) => out
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => native[digit as usize],
+                None => c,
+            })
+            .collect(),
+        None => out,
+    }
+}
+
+/// The minus sign of the language `tag`.
+pub(super) fn minus_sign(tag: &str) -> &'static str {
+    lookup(NUMBER_DATA, tag).minus
+}
+
+/// The native digits of the language `tag`, `None` when it uses ASCII digits.
+pub(super) fn native_digits(tag: &str) -> Option<&'static [char; 10]> {
+    lookup(NUMBER_DATA, tag).digits.as_ref()
+}
+
+/// Scales the number down to its compact unit, returning the unit suffix.
+///
+/// The unit is picked again once the number is rounded, `999_999` being `1M` and not `1,000K`.
+fn compact(number: Number, data: &NumberData) -> (Number, &'static str, (usize, usize)) {
+    let unit = |value: f64| {
+        let magnitude = if value < 1.0 {
+            0
+        } else {
+            value.log10().floor() as u32
+        };
+        data.compact
+            .iter()
+            .rev()
+            .find(|(exponent, _)| *exponent <= magnitude)
+    };
+
+    let mut value = number.to_f64().abs();
+    loop {
+        let Some((exponent, suffix)) = unit(value) else {
+            // Whole numbers have no fraction here, rounding 999.5 up to 1000
+            let rounded = value.round();
+            if rounded > value && unit(rounded).is_some() {
+                value = rounded;
+                continue;
+            }
+            return (number, "", (0, 0));
+        };
+
+        let power = 10f64.powi(*exponent as i32);
+        let scaled = number.to_f64() / power;
+        // Two significant digits, like CLDR's short compact format
+        let max_fraction = if scaled.abs() < 10.0 { 1 } else { 0 };
+        let precision = 10f64.powi(max_fraction);
+        let rounded = (scaled.abs() * precision).round() / precision * power;
+        match unit(rounded) {
+            Some((next, _)) if next > exponent => value = rounded,
+            _ => return (Number::Float(scaled), suffix, (0, max_fraction as usize)),
+        }
+    }
+}
+
+/// Absolute value split into its integer digits and fraction digits.
+fn digits(number: Number, min_fraction: usize, max_fraction: usize) -> (String, String) {
+    let formatted = match number {
+        Number::Integer(value) => format!("{}.{}", value.unsigned_abs(), "0".repeat(max_fraction)),
+        Number::Float(value) => format!("{:.*}", max_fraction, value.abs()),
+    };
+
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut fraction = fraction.to_string();
+    while fraction.len() > min_fraction && fraction.ends_with('0') {
+        fraction.pop();
+    }
+
+    (integer.to_string(), fraction)
+}
+
+fn group(integer: &str, data: &NumberData) -> String {
+    const PRIMARY: usize = 3;
+
+    if integer.len() < PRIMARY + data.min_grouping_digits {
+        return integer.to_string();
+    }
+
+    let (rest, last) = integer.split_at(integer.len() - PRIMARY);
+    let mut groups = vec![last];
+    let mut rest = rest;
+    while rest.len() > data.secondary_grouping {
+        let (head, tail) = rest.split_at(rest.len() - data.secondary_grouping);
+        groups.push(tail);
+        rest = head;
+    }
+    if !rest.is_empty() {
+        groups.push(rest);
+    }
+    groups.reverse();
+    groups.join(data.group)
+}
+
+struct NumberData {
+    decimal: &'static str,
+    group: &'static str,
+    minus: &'static str,
+    percent_suffix: &'static str,
+    /// Size of the groups after the first one, 2 for the Indian `12,34,567`
+    secondary_grouping: usize,
+    /// Digits needed before the first group separator is used
+    min_grouping_digits: usize,
+    digits: Option<[char; 10]>,
+    /// Short compact units as (power of ten, suffix)
+    compact: &'static [(u32, &'static str)],
+}
+
+const LATN: NumberData = NumberData {
+    decimal: ".",
+    group: ",",
+    minus: "-",
+    percent_suffix: "%",
+    secondary_grouping: 3,
+    min_grouping_digits: 1,
+    digits: None,
+    compact: &[],
+};
+
+static NUMBER_DATA: &[(&str, NumberData)] = &[
+    (
+        "en",
+        NumberData {
+            compact: &[(3, "K"), (6, "M"), (9, "B"), (12, "T")],
+            ..LATN
+        },
+    ),
+    (
+        "ar",
+        NumberData {
+            decimal: "٫",
+            group: "٬",
+            minus: "\u{61c}-",
+            percent_suffix: "٪\u{61c}",
+            digits: Some(['٠', '١', '٢', '٣', '٤', '٥', '٦', '٧', '٨', '٩']),
+            compact: &[
+                (3, "\u{a0}ألف"),
+                (6, "\u{a0}مليون"),
+                (9, "\u{a0}مليار"),
+                (12, "\u{a0}ترليون"),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "da",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            percent_suffix: "\u{a0}%",
+            compact: &[
+                (3, "\u{a0}t"),
+                (6, "\u{a0}mio."),
+                (9, "\u{a0}mia."),
+                (12, "\u{a0}bio."),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "de",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            percent_suffix: "\u{a0}%",
+            compact: &[(6, "\u{a0}Mio."), (9, "\u{a0}Mrd."), (12, "\u{a0}Bio.")],
+            ..LATN
+        },
+    ),
+    (
+        "es",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            percent_suffix: "\u{a0}%",
+            min_grouping_digits: 2,
+            compact: &[(3, "\u{a0}mil"), (6, "\u{a0}M"), (12, "\u{a0}B")],
+            ..LATN
+        },
+    ),
+    (
+        "fr",
+        NumberData {
+            decimal: ",",
+            group: "\u{202f}",
+            percent_suffix: "\u{202f}%",
+            compact: &[
+                (3, "\u{a0}k"),
+                (6, "\u{a0}M"),
+                (9, "\u{a0}Md"),
+                (12, "\u{a0}Bn"),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "hi",
+        NumberData {
+            secondary_grouping: 2,
+            compact: &[
+                (3, "\u{a0}हज़ार"),
+                (5, "\u{a0}लाख"),
+                (7, "\u{a0}क॰"),
+                (9, "\u{a0}अ॰"),
+                (11, "\u{a0}ख॰"),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "it",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            compact: &[(6, "\u{a0}Mln"), (9, "\u{a0}Mrd"), (12, "\u{a0}Bln")],
+            ..LATN
+        },
+    ),
+    (
+        "ja",
+        NumberData {
+            compact: &[(4, "万"), (8, "億"), (12, "兆")],
+            ..LATN
+        },
+    ),
+    (
+        "ko",
+        NumberData {
+            compact: &[(3, "천"), (4, "만"), (8, "억"), (12, "조")],
+            ..LATN
+        },
+    ),
+    (
+        "nl",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            compact: &[
+                (3, "K"),
+                (6, "\u{a0}mln."),
+                (9, "\u{a0}mld."),
+                (12, "\u{a0}bln."),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "pt",
+        NumberData {
+            decimal: ",",
+            group: ".",
+            compact: &[
+                (3, "\u{a0}mil"),
+                (6, "\u{a0}mi"),
+                (9, "\u{a0}bi"),
+                (12, "\u{a0}tri"),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "ru",
+        NumberData {
+            decimal: ",",
+            group: "\u{a0}",
+            percent_suffix: "\u{a0}%",
+            compact: &[
+                (3, "\u{a0}тыс."),
+                (6, "\u{a0}млн"),
+                (9, "\u{a0}млрд"),
+                (12, "\u{a0}трлн"),
+            ],
+            ..LATN
+        },
+    ),
+    (
+        "zh",
+        NumberData {
+            compact: &[(4, "万"), (8, "亿"), (12, "万亿")],
+            ..LATN
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(tag: &str, number: impl Into<Number>, options: NumberOptions) -> String {
+        format_number(tag, number.into(), &options)
+    }
+
+    #[test]
+    fn test_grouping() {
+        let options = NumberOptions::default();
+        assert_eq!(format("en", 1234567, options), "1,234,567");
+        assert_eq!(format("en", -1234.5678, options), "-1,234.568");
+        assert_eq!(format("ru", 1234567, options), "1\u{a0}234\u{a0}567");
+        assert_eq!(format("hi", 123456789, options), "12,34,56,789");
+        assert_eq!(format("es", 1234, options), "1234");
+        assert_eq!(format("es", 12345, options), "12.345");
+        assert_eq!(format("ar", 1234.5, options), "١٬٢٣٤٫٥");
+    }
+
+    #[test]
+    fn test_fraction_digits() {
+        let options = NumberOptions {
+            min_fraction_digits: Some(2),
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("de", 1234, options), "1.234,00");
+        assert_eq!(format("de", 1234.5, options), "1.234,50");
+
+        let options = NumberOptions {
+            max_fraction_digits: Some(1),
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("en", 2.25, options), "2.2");
+        assert_eq!(format("en", -0.01, options), "0");
+
+        let options = NumberOptions {
+            style: NumberStyle::Integer,
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("en", 2.7, options), "3");
+    }
+
+    #[test]
+    fn test_percent() {
+        let options = NumberOptions {
+            style: NumberStyle::Percent,
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("en", 0.256, options), "26%");
+        assert_eq!(format("de", 1, options), "100\u{a0}%");
+    }
+
+    #[test]
+    fn test_compact() {
+        let options = NumberOptions {
+            style: NumberStyle::Compact,
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("en", 999, options), "999");
+        assert_eq!(format("en", 1234, options), "1.2K");
+        assert_eq!(format("en", 12345, options), "12K");
+        assert_eq!(format("en", 1234567, options), "1.2M");
+        assert_eq!(format("de", 1234, options), "1.234");
+        assert_eq!(format("es", 1_500_000_000u64, options), "1500\u{a0}M");
+        assert_eq!(format("ja", 123456, options), "12万");
+    }
+
+    #[test]
+    fn test_compact_rounding_up_to_the_next_unit() {
+        let options = NumberOptions {
+            style: NumberStyle::Compact,
+            ..NumberOptions::default()
+        };
+        assert_eq!(format("en", 999.5, options), "1K");
+        assert_eq!(format("en", 999_499, options), "999K");
+        assert_eq!(format("en", 999_950, options), "1M");
+        assert_eq!(format("en", 999_999, options), "1M");
+        assert_eq!(format("en", 999_999_999, options), "1B");
+        assert_eq!(format("en", -999_999, options), "-1M");
+        assert_eq!(format("de", 999_999.9, options), "1\u{a0}Mio.");
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_english() {
+        assert_eq!(
+            format("xx-YY", 1234.5, NumberOptions::default()),
+            "1,234.5"
+        );
+        assert_eq!(
+            format("zh-CN", 1234.5, NumberOptions::default()),
+            "1,234.5"
+        );
+    }
+}
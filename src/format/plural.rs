@@ -0,0 +1,65 @@
+/// CLDR plural category of a count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Plural {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// The cardinal plural category of the integer `n` in the language `tag`.
+pub(super) fn plural_category(tag: &str, n: u64) -> Plural {
+    let tag = tag.replace('_', "-").to_ascii_lowercase();
+    let language = tag.split('-').next().unwrap_or_default();
+
+    match language {
+        "ar" => match (n, n % 100) {
+            (0, _) => Plural::Zero,
+            (1, _) => Plural::One,
+            (2, _) => Plural::Two,
+            (_, 3..=10) => Plural::Few,
+            (_, 11..=99) => Plural::Many,
+            _ => Plural::Other,
+        },
+        "ru" | "uk" => match (n % 10, n % 100) {
+            (1, rem) if rem != 11 => Plural::One,
+            (2..=4, rem) if !(12..=14).contains(&rem) => Plural::Few,
+            _ => Plural::Many,
+        },
+        "fr" | "pt" | "hi" if n <= 1 => Plural::One,
+        "ja" | "ko" | "zh" => Plural::Other,
+        _ if n == 1 => Plural::One,
+        _ => Plural::Other,
+    }
+}
+
+/// Picks the pattern for `plural`, the last one when the category has no pattern of its own.
+pub(super) fn select(patterns: &'static [(Plural, &'static str)], plural: Plural) -> &'static str {
+    patterns
+        .iter()
+        .find(|(category, _)| *category == plural)
+        .or(patterns.last())
+        .map(|(_, pattern)| *pattern)
+        .unwrap_or("{0}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_plural_categories() {
+        assert_eq!(plural_category("en", 1), Plural::One);
+        assert_eq!(plural_category("en", 0), Plural::Other);
+        assert_eq!(plural_category("fr", 0), Plural::One);
+        assert_eq!(plural_category("ru", 21), Plural::One);
+        assert_eq!(plural_category("ru", 12), Plural::Many);
+        assert_eq!(plural_category("ru", 34), Plural::Few);
+        assert_eq!(plural_category("ar", 2), Plural::Two);
+        assert_eq!(plural_category("ar", 105), Plural::Few);
+        assert_eq!(plural_category("ar", 111), Plural::Many);
+        assert_eq!(plural_category("zh_CN", 1), Plural::Other);
+    }
+}
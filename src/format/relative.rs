@@ -0,0 +1,542 @@
+use std::time::{Duration, SystemTime};
+
+use super::{
+    lookup,
+    number::{format_number, NumberOptions, NumberStyle},
+    plural::{plural_category, select, Plural},
+};
+
+/// A value for a `{name, relative_time}` placeholder: an offset from now, negative in the past.
+///
+/// A [`SystemTime`] is turned into its offset from the current time, so passing the time of an
+/// event gives "3 minutes ago".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelativeTime {
+    seconds: i64,
+}
+
+impl RelativeTime {
+    /// An offset of `seconds`, negative for the past.
+    pub fn from_seconds(seconds: i64) -> Self {
+        Self { seconds }
+    }
+
+    /// `duration` from now, "in 2 days".
+    pub fn future(duration: Duration) -> Self {
+        Self::from_seconds(i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+    }
+
+    /// `duration` before now, "2 days ago".
+    pub fn past(duration: Duration) -> Self {
+        Self::from_seconds(-i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<SystemTime> for RelativeTime {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(SystemTime::now()) {
+            Ok(duration) => RelativeTime::future(duration),
+            Err(err) => RelativeTime::past(err.duration()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::TimeDelta> for RelativeTime {
+    fn from(delta: chrono::TimeDelta) -> Self {
+        RelativeTime::from_seconds(delta.num_seconds())
+    }
+}
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// Formats `value` in the largest whole unit, "3 minutes ago" or "in 2 days", using the plural
+/// forms of the language `tag`.
+///
+/// ```
+/// use i18nify::format::{format_relative_time, RelativeTime};
+///
+/// assert_eq!(format_relative_time("en", RelativeTime::from_seconds(-200)), "3 minutes ago");
+/// assert_eq!(format_relative_time("de", RelativeTime::from_seconds(2 * 86_400)), "in 2 Tagen");
+/// assert_eq!(format_relative_time("ru", RelativeTime::from_seconds(-5 * 3600)), "5 часов назад");
+/// ```
+pub fn format_relative_time(tag: &str, value: RelativeTime) -> String {
+    let data = lookup(RELATIVE_DATA, tag);
+    let seconds = value.seconds.unsigned_abs();
+    if seconds == 0 {
+        return data.now.to_string();
+    }
+
+    let (unit, count) = match seconds {
+        s if s < MINUTE => (0, s),
+        s if s < HOUR => (1, s / MINUTE),
+        s if s < DAY => (2, s / HOUR),
+        s if s < 7 * DAY => (3, s / DAY),
+        s if s < 30 * DAY => (4, s / (7 * DAY)),
+        s if s < 365 * DAY => (5, s / (30 * DAY)),
+        s => (6, s / (365 * DAY)),
+    };
+
+    let pattern = select(data.units[unit], plural_category(tag, count));
+    let amount = pattern.replace("{0}", &format_count(tag, count));
+    let frame = if value.seconds < 0 {
+        data.past
+    } else {
+        data.future
+    };
+    frame.replace("{0}", &amount)
+}
+
+/// Formats `value` as days, hours, minutes and seconds in the short units of the language `tag`,
+/// leaving out the zero ones.
+///
+/// ```
+/// use std::time::Duration;
+/// use i18nify::format::format_duration;
+///
+/// assert_eq!(format_duration("en", Duration::from_secs(7500)), "2 h 5 min");
+/// assert_eq!(format_duration("zh", Duration::from_secs(7500)), "2小时5分钟");
+/// ```
+pub fn format_duration(tag: &str, value: Duration) -> String {
+    let data = lookup(RELATIVE_DATA, tag);
+    let seconds = value.as_secs();
+    let parts = [
+        seconds / DAY,
+        seconds % DAY / HOUR,
+        seconds % HOUR / MINUTE,
+        seconds % MINUTE,
+    ];
+
+    let formatted = parts
+        .iter()
+        .zip(data.duration_units)
+        .filter(|(count, _)| **count > 0)
+        .map(|(count, unit)| unit.replace("{0}", &format_count(tag, *count)))
+        .collect::<Vec<_>>();
+
+    if formatted.is_empty() {
+        data.duration_units[3].replace("{0}", &format_count(tag, 0))
+    } else {
+        formatted.join(data.duration_separator)
+    }
+}
+
+fn format_count(tag: &str, count: u64) -> String {
+    let options = NumberOptions {
+        style: NumberStyle::Integer,
+        ..NumberOptions::default()
+    };
+    format_number(tag, count.into(), &options)
+}
+
+type Patterns = &'static [(Plural, &'static str)];
+
+struct RelativeData {
+    /// Surrounds the amount for offsets in the future, e.g. `in {0}`
+    future: &'static str,
+    past: &'static str,
+    now: &'static str,
+    /// Amounts of seconds, minutes, hours, days, weeks, months and years per plural category
+    units: [Patterns; 7],
+    /// Short days, hours, minutes and seconds
+    duration_units: [&'static str; 4],
+    duration_separator: &'static str,
+}
+
+static RELATIVE_DATA: &[(&str, RelativeData)] = &[
+    (
+        "en",
+        RelativeData {
+            future: "in {0}",
+            past: "{0} ago",
+            now: "now",
+            units: [
+                &[(Plural::One, "{0} second"), (Plural::Other, "{0} seconds")],
+                &[(Plural::One, "{0} minute"), (Plural::Other, "{0} minutes")],
+                &[(Plural::One, "{0} hour"), (Plural::Other, "{0} hours")],
+                &[(Plural::One, "{0} day"), (Plural::Other, "{0} days")],
+                &[(Plural::One, "{0} week"), (Plural::Other, "{0} weeks")],
+                &[(Plural::One, "{0} month"), (Plural::Other, "{0} months")],
+                &[(Plural::One, "{0} year"), (Plural::Other, "{0} years")],
+            ],
+            duration_units: ["{0} d", "{0} h", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "ar",
+        RelativeData {
+            future: "خلال {0}",
+            past: "قبل {0}",
+            now: "الآن",
+            units: [
+                &[
+                    (Plural::One, "ثانية واحدة"),
+                    (Plural::Two, "ثانيتين"),
+                    (Plural::Few, "{0} ثوانٍ"),
+                    (Plural::Other, "{0} ثانية"),
+                ],
+                &[
+                    (Plural::One, "دقيقة واحدة"),
+                    (Plural::Two, "دقيقتين"),
+                    (Plural::Few, "{0} دقائق"),
+                    (Plural::Other, "{0} دقيقة"),
+                ],
+                &[
+                    (Plural::One, "ساعة واحدة"),
+                    (Plural::Two, "ساعتين"),
+                    (Plural::Few, "{0} ساعات"),
+                    (Plural::Other, "{0} ساعة"),
+                ],
+                &[
+                    (Plural::One, "يوم واحد"),
+                    (Plural::Two, "يومين"),
+                    (Plural::Few, "{0} أيام"),
+                    (Plural::Other, "{0} يوم"),
+                ],
+                &[
+                    (Plural::One, "أسبوع واحد"),
+                    (Plural::Two, "أسبوعين"),
+                    (Plural::Few, "{0} أسابيع"),
+                    (Plural::Other, "{0} أسبوع"),
+                ],
+                &[
+                    (Plural::One, "شهر واحد"),
+                    (Plural::Two, "شهرين"),
+                    (Plural::Few, "{0} أشهر"),
+                    (Plural::Other, "{0} شهر"),
+                ],
+                &[
+                    (Plural::One, "سنة واحدة"),
+                    (Plural::Two, "سنتين"),
+                    (Plural::Few, "{0} سنوات"),
+                    (Plural::Other, "{0} سنة"),
+                ],
+            ],
+            duration_units: ["{0} ي", "{0} س", "{0} د", "{0} ث"],
+            duration_separator: " و",
+        },
+    ),
+    (
+        "da",
+        RelativeData {
+            future: "om {0}",
+            past: "for {0} siden",
+            now: "nu",
+            units: [
+                &[(Plural::One, "{0} sekund"), (Plural::Other, "{0} sekunder")],
+                &[(Plural::One, "{0} minut"), (Plural::Other, "{0} minutter")],
+                &[(Plural::One, "{0} time"), (Plural::Other, "{0} timer")],
+                &[(Plural::One, "{0} dag"), (Plural::Other, "{0} dage")],
+                &[(Plural::One, "{0} uge"), (Plural::Other, "{0} uger")],
+                &[(Plural::One, "{0} måned"), (Plural::Other, "{0} måneder")],
+                &[(Plural::Other, "{0} år")],
+            ],
+            duration_units: ["{0} d.", "{0} t.", "{0} min.", "{0} sek."],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "de",
+        RelativeData {
+            future: "in {0}",
+            past: "vor {0}",
+            now: "jetzt",
+            units: [
+                &[
+                    (Plural::One, "{0} Sekunde"),
+                    (Plural::Other, "{0} Sekunden"),
+                ],
+                &[(Plural::One, "{0} Minute"), (Plural::Other, "{0} Minuten")],
+                &[(Plural::One, "{0} Stunde"), (Plural::Other, "{0} Stunden")],
+                &[(Plural::One, "{0} Tag"), (Plural::Other, "{0} Tagen")],
+                &[(Plural::One, "{0} Woche"), (Plural::Other, "{0} Wochen")],
+                &[(Plural::One, "{0} Monat"), (Plural::Other, "{0} Monaten")],
+                &[(Plural::One, "{0} Jahr"), (Plural::Other, "{0} Jahren")],
+            ],
+            duration_units: ["{0} T.", "{0} Std.", "{0} Min.", "{0} Sek."],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "es",
+        RelativeData {
+            future: "dentro de {0}",
+            past: "hace {0}",
+            now: "ahora",
+            units: [
+                &[
+                    (Plural::One, "{0} segundo"),
+                    (Plural::Other, "{0} segundos"),
+                ],
+                &[(Plural::One, "{0} minuto"), (Plural::Other, "{0} minutos")],
+                &[(Plural::One, "{0} hora"), (Plural::Other, "{0} horas")],
+                &[(Plural::One, "{0} día"), (Plural::Other, "{0} días")],
+                &[(Plural::One, "{0} semana"), (Plural::Other, "{0} semanas")],
+                &[(Plural::One, "{0} mes"), (Plural::Other, "{0} meses")],
+                &[(Plural::One, "{0} año"), (Plural::Other, "{0} años")],
+            ],
+            duration_units: ["{0} d", "{0} h", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "fr",
+        RelativeData {
+            future: "dans {0}",
+            past: "il y a {0}",
+            now: "maintenant",
+            units: [
+                &[
+                    (Plural::One, "{0} seconde"),
+                    (Plural::Other, "{0} secondes"),
+                ],
+                &[(Plural::One, "{0} minute"), (Plural::Other, "{0} minutes")],
+                &[(Plural::One, "{0} heure"), (Plural::Other, "{0} heures")],
+                &[(Plural::One, "{0} jour"), (Plural::Other, "{0} jours")],
+                &[
+                    (Plural::One, "{0} semaine"),
+                    (Plural::Other, "{0} semaines"),
+                ],
+                &[(Plural::Other, "{0} mois")],
+                &[(Plural::One, "{0} an"), (Plural::Other, "{0} ans")],
+            ],
+            duration_units: ["{0} j", "{0} h", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "hi",
+        RelativeData {
+            future: "{0} में",
+            past: "{0} पहले",
+            now: "अब",
+            units: [
+                &[(Plural::Other, "{0} सेकंड")],
+                &[(Plural::Other, "{0} मिनट")],
+                &[(Plural::Other, "{0} घंटे")],
+                &[(Plural::Other, "{0} दिन")],
+                &[(Plural::Other, "{0} सप्ताह")],
+                &[(Plural::Other, "{0} माह")],
+                &[(Plural::Other, "{0} वर्ष")],
+            ],
+            duration_units: ["{0} दिन", "{0} घं॰", "{0} मि॰", "{0} से॰"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "it",
+        RelativeData {
+            future: "tra {0}",
+            past: "{0} fa",
+            now: "ora",
+            units: [
+                &[(Plural::One, "{0} secondo"), (Plural::Other, "{0} secondi")],
+                &[(Plural::One, "{0} minuto"), (Plural::Other, "{0} minuti")],
+                &[(Plural::One, "{0} ora"), (Plural::Other, "{0} ore")],
+                &[(Plural::One, "{0} giorno"), (Plural::Other, "{0} giorni")],
+                &[
+                    (Plural::One, "{0} settimana"),
+                    (Plural::Other, "{0} settimane"),
+                ],
+                &[(Plural::One, "{0} mese"), (Plural::Other, "{0} mesi")],
+                &[(Plural::One, "{0} anno"), (Plural::Other, "{0} anni")],
+            ],
+            duration_units: ["{0} g", "{0} h", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "ja",
+        RelativeData {
+            future: "{0}後",
+            past: "{0}前",
+            now: "今",
+            units: [
+                &[(Plural::Other, "{0} 秒")],
+                &[(Plural::Other, "{0} 分")],
+                &[(Plural::Other, "{0} 時間")],
+                &[(Plural::Other, "{0} 日")],
+                &[(Plural::Other, "{0} 週間")],
+                &[(Plural::Other, "{0} か月")],
+                &[(Plural::Other, "{0} 年")],
+            ],
+            duration_units: ["{0}日", "{0}時間", "{0}分", "{0}秒"],
+            duration_separator: "",
+        },
+    ),
+    (
+        "ko",
+        RelativeData {
+            future: "{0} 후",
+            past: "{0} 전",
+            now: "지금",
+            units: [
+                &[(Plural::Other, "{0}초")],
+                &[(Plural::Other, "{0}분")],
+                &[(Plural::Other, "{0}시간")],
+                &[(Plural::Other, "{0}일")],
+                &[(Plural::Other, "{0}주")],
+                &[(Plural::Other, "{0}개월")],
+                &[(Plural::Other, "{0}년")],
+            ],
+            duration_units: ["{0}일", "{0}시간", "{0}분", "{0}초"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "nl",
+        RelativeData {
+            future: "over {0}",
+            past: "{0} geleden",
+            now: "nu",
+            units: [
+                &[
+                    (Plural::One, "{0} seconde"),
+                    (Plural::Other, "{0} seconden"),
+                ],
+                &[(Plural::One, "{0} minuut"), (Plural::Other, "{0} minuten")],
+                &[(Plural::Other, "{0} uur")],
+                &[(Plural::One, "{0} dag"), (Plural::Other, "{0} dagen")],
+                &[(Plural::One, "{0} week"), (Plural::Other, "{0} weken")],
+                &[(Plural::One, "{0} maand"), (Plural::Other, "{0} maanden")],
+                &[(Plural::Other, "{0} jaar")],
+            ],
+            duration_units: ["{0} d", "{0} u", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "pt",
+        RelativeData {
+            future: "em {0}",
+            past: "há {0}",
+            now: "agora",
+            units: [
+                &[
+                    (Plural::One, "{0} segundo"),
+                    (Plural::Other, "{0} segundos"),
+                ],
+                &[(Plural::One, "{0} minuto"), (Plural::Other, "{0} minutos")],
+                &[(Plural::One, "{0} hora"), (Plural::Other, "{0} horas")],
+                &[(Plural::One, "{0} dia"), (Plural::Other, "{0} dias")],
+                &[(Plural::One, "{0} semana"), (Plural::Other, "{0} semanas")],
+                &[(Plural::One, "{0} mês"), (Plural::Other, "{0} meses")],
+                &[(Plural::One, "{0} ano"), (Plural::Other, "{0} anos")],
+            ],
+            duration_units: ["{0} d", "{0} h", "{0} min", "{0} s"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "ru",
+        RelativeData {
+            future: "через {0}",
+            past: "{0} назад",
+            now: "сейчас",
+            units: [
+                &[
+                    (Plural::One, "{0} секунду"),
+                    (Plural::Few, "{0} секунды"),
+                    (Plural::Many, "{0} секунд"),
+                ],
+                &[
+                    (Plural::One, "{0} минуту"),
+                    (Plural::Few, "{0} минуты"),
+                    (Plural::Many, "{0} минут"),
+                ],
+                &[
+                    (Plural::One, "{0} час"),
+                    (Plural::Few, "{0} часа"),
+                    (Plural::Many, "{0} часов"),
+                ],
+                &[
+                    (Plural::One, "{0} день"),
+                    (Plural::Few, "{0} дня"),
+                    (Plural::Many, "{0} дней"),
+                ],
+                &[
+                    (Plural::One, "{0} неделю"),
+                    (Plural::Few, "{0} недели"),
+                    (Plural::Many, "{0} недель"),
+                ],
+                &[
+                    (Plural::One, "{0} месяц"),
+                    (Plural::Few, "{0} месяца"),
+                    (Plural::Many, "{0} месяцев"),
+                ],
+                &[
+                    (Plural::One, "{0} год"),
+                    (Plural::Few, "{0} года"),
+                    (Plural::Many, "{0} лет"),
+                ],
+            ],
+            duration_units: ["{0} дн.", "{0} ч", "{0} мин", "{0} с"],
+            duration_separator: " ",
+        },
+    ),
+    (
+        "zh",
+        RelativeData {
+            future: "{0}后",
+            past: "{0}前",
+            now: "现在",
+            units: [
+                &[(Plural::Other, "{0}秒钟")],
+                &[(Plural::Other, "{0}分钟")],
+                &[(Plural::Other, "{0}小时")],
+                &[(Plural::Other, "{0}天")],
+                &[(Plural::Other, "{0}周")],
+                &[(Plural::Other, "{0}个月")],
+                &[(Plural::Other, "{0}年")],
+            ],
+            duration_units: ["{0}天", "{0}小时", "{0}分钟", "{0}秒"],
+            duration_separator: "",
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn relative(tag: &str, seconds: i64) -> String {
+        format_relative_time(tag, RelativeTime::from_seconds(seconds))
+    }
+
+    #[test]
+    fn test_relative_time_units() {
+        assert_eq!(relative("en", 0), "now");
+        assert_eq!(relative("en", -1), "1 second ago");
+        assert_eq!(relative("en", 3600), "in 1 hour");
+        assert_eq!(relative("en", -10 * 86_400), "1 week ago");
+        assert_eq!(relative("en", 400 * 86_400), "in 1 year");
+        assert_eq!(relative("fr", -90 * 86_400), "il y a 3 mois");
+        assert_eq!(relative("ja", -120), "2 分前");
+    }
+
+    #[test]
+    fn test_relative_time_plurals() {
+        assert_eq!(relative("ru", -21 * 60), "21 минуту назад");
+        assert_eq!(relative("ru", 3 * 86_400), "через 3 дня");
+        assert_eq!(relative("ar", -2 * 3600), "قبل ساعتين");
+        assert_eq!(relative("ar", -5 * 3600), "قبل ٥ ساعات");
+        assert_eq!(relative("da", -365 * 86_400), "for 1 år siden");
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(format_duration("en", Duration::from_secs(0)), "0 s");
+        assert_eq!(
+            format_duration("en", Duration::from_secs(90_061)),
+            "1 d 1 h 1 min 1 s"
+        );
+        assert_eq!(
+            format_duration("de", Duration::from_secs(3605)),
+            "1 Std. 5 Sek."
+        );
+        assert_eq!(format_duration("ko", Duration::from_secs(125)), "2분 5초");
+    }
+}
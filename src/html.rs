@@ -0,0 +1,156 @@
+//! HTML-safe rendering of translations.
+//!
+//! With `html = true` the derive generates a `<key>_html` method next to every message method.
+//! It HTML-escapes the interpolated values but keeps the translation itself, including any markup
+//! the translators wrote, and returns a [`SafeHtml`] so templates can insert it without escaping
+//! it a second time.
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/html_locales", html = true)]
+//!     pub struct HtmlLocale;
+//! }
+//! use demo::{Locale, Name};
+//!
+//! let html = Locale::En.welcome_html(Name("<script>alert(1)</script>"));
+//! assert_eq!(
+//!     html.as_str(),
+//!     "Welcome, <b>&lt;script&gt;alert(1)&lt;/script&gt;</b>!"
+//! );
+//! ```
+
+use std::fmt::{self, Display, Write};
+
+/// A string of HTML that is safe to insert into a page as is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SafeHtml(String);
+
+impl SafeHtml {
+    /// Wraps `html` without escaping it, the caller vouches that it is safe.
+    pub fn from_trusted(html: String) -> Self {
+        SafeHtml(html)
+    }
+
+    /// Escapes `text` so it shows up literally in a page.
+    ///
+    /// ```
+    /// use i18nify::html::SafeHtml;
+    ///
+    /// assert_eq!(SafeHtml::escape("Tom & \"Jerry\"").as_str(), "Tom &amp; &quot;Jerry&quot;");
+    /// ```
+    pub fn escape(text: &str) -> Self {
+        SafeHtml(Escape(text).to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Display for SafeHtml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for SafeHtml {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<SafeHtml> for String {
+    fn from(html: SafeHtml) -> Self {
+        html.0
+    }
+}
+
+/// Inserted as is by askama templates, without the `|safe` filter.
+#[cfg(feature = "askama")]
+impl askama::filters::HtmlSafe for SafeHtml {}
+
+/// Inserted as is by maud's `html!`, like a [`maud::PreEscaped`].
+#[cfg(feature = "maud")]
+impl maud::Render for SafeHtml {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&self.0);
+    }
+}
+
+#[cfg(feature = "maud")]
+impl From<SafeHtml> for maud::PreEscaped<String> {
+    fn from(html: SafeHtml) -> Self {
+        maud::PreEscaped(html.0)
+    }
+}
+
+/// Displays its value with `&`, `<`, `>`, `"` and `'` escaped.
+#[derive(Debug, Clone, Copy)]
+pub struct Escape<T>(pub T);
+
+impl<T: Display> Display for Escape<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(EscapingWriter(f), "{}", self.0)
+    }
+}
+
+struct EscapingWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl Write for EscapingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut last = 0;
+        for (idx, c) in s.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                '\'' => "&#x27;",
+                _ => continue,
+            };
+            self.0.write_str(&s[last..idx])?;
+            self.0.write_str(escaped)?;
+            last = idx + 1;
+        }
+        self.0.write_str(&s[last..])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    #[cfg(feature = "askama")]
+    fn test_askama_does_not_escape_again() {
+        use super::SafeHtml;
+        use askama::Template;
+
+        #[derive(Template)]
+        #[template(source = "<p>{{ message }} {{ name }}</p>", ext = "html")]
+        struct Page {
+            message: SafeHtml,
+            name: &'static str,
+        }
+
+        let page = Page {
+            message: SafeHtml::from_trusted("<b>Welcome</b>".to_string()),
+            name: "<Ada>",
+        };
+        assert_eq!(page.render().unwrap(), "<p><b>Welcome</b> &#60;Ada&#62;</p>");
+    }
+
+    #[test]
+    #[cfg(feature = "maud")]
+    fn test_maud_does_not_escape_again() {
+        use super::SafeHtml;
+
+        let message = SafeHtml::from_trusted("<b>Welcome</b>".to_string());
+        let page = maud::html! { p { (message) " " ("<Ada>") } };
+        assert_eq!(page.into_string(), "<p><b>Welcome</b> &lt;Ada&gt;</p>");
+    }
+}
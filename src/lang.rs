@@ -0,0 +1,81 @@
+//! The `Lang` extractor shared by the axum and actix-web integrations, each implementing the
+//! extractor trait of its framework.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{accept_language, Internationalization};
+
+/// Extracts the locale of the request.
+///
+/// Uses the locale resolved by the middleware of the framework when it is present. Without it the
+/// `Accept-Language` header is negotiated on the fly with `T::default()`, falling back to
+/// [`Internationalization::default_language`].
+pub struct Lang<T: Internationalization>(pub T::Item);
+
+impl<T> Lang<T>
+where
+    T: Internationalization + Default,
+    T::Item: Clone,
+{
+    /// The locale stored in the request extensions, else the one negotiated for the values of the
+    /// `Accept-Language` headers.
+    pub(crate) fn resolve<'a>(
+        extension: Option<&T::Item>,
+        accept_language: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Result<Self, LangRejection> {
+        if let Some(item) = extension {
+            return Ok(Lang(item.clone()));
+        }
+
+        accept_language::negotiate_default::<T>(accept_language)
+            .map(Lang)
+            .ok_or(LangRejection::InvalidAcceptLanguage)
+    }
+}
+
+impl<T: Internationalization> Deref for Lang<T> {
+    type Target = T::Item;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Internationalization> DerefMut for Lang<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> fmt::Debug for Lang<T>
+where
+    T: Internationalization,
+    T::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lang").field(&self.0).finish()
+    }
+}
+
+/// Rejection of the [`Lang`] extractor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LangRejection {
+    /// The `Accept-Language` header is not visible ASCII.
+    InvalidAcceptLanguage,
+}
+
+impl fmt::Display for LangRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LangRejection::InvalidAcceptLanguage => {
+                f.write_str("Failed to resolve the locale: invalid `Accept-Language` header")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LangRejection {}
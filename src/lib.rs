@@ -30,16 +30,38 @@
 //!     #[i18n(folder = "tests/doc_locales")]
 //!     pub struct DocLocale;
 //! }
-//! 
-//! fn main() {
-//!     // Based on the `Locale` enum type to retrieve internationalized text
-//!     let hello = demo::Locale::En.hello_world();
-//!     println!("{}",hello);// Hello, World!
-//!     
-//!     // Based on the `Internationalize` trait implemented with `DocLocale` to retrieve internationalized text
-//!     let greeting = DocLocale::da().greeting(Name("John"));
-//!     println!("{}",greeting);// Hej John
-//!}
+//!
+//! // Based on the `Locale` enum type to retrieve internationalized text
+//! let hello = demo::Locale::En.hello_world();
+//! println!("{}",hello);// Hello, World!
+//!
+//! // Based on the `Internationalize` trait implemented with `DocLocale` to retrieve internationalized text
+//! let greeting = demo::DocLocale.da().greeting(demo::Name("John"));
+//! println!("{}",greeting);// Hej John
+//! ```
+//!
+//! ## Pseudo-localization
+//!
+//! `pseudo = "en-XA"` adds a locale translated from the default one, `en` without a default, to
+//! find hardcoded strings and text that gets cut off before real translations exist. Its letters
+//! are accented, its text put in brackets and lengthened by `pseudo_expansion` percent, 35 unless
+//! set. A tag with the `XB` region such as `ar-XB` shows the text right to left instead.
+//! Placeholders and markup tags are kept as they are:
+//!
+//! ```rust
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/doc_locales", pseudo = "en-XA", pseudo = "ar-XB")]
+//!     pub struct DocLocale;
+//! }
+//! use demo::{Locale, Name};
+//! use i18nify::bidi::Direction;
+//!
+//! assert_eq!(Locale::EnXa.greeting(Name("Ada")), "[Ĥéļļö Ada one]");
+//! assert_eq!(Locale::EnXa.language_tag(), "en-XA");
+//! assert_eq!(Locale::ArXb.greeting(Name("Ada")), "\u{202e}Hello \u{202c}Ada");
+//! assert_eq!(Locale::ArXb.direction(), Direction::Rtl);
 //! ```
 //! 
 
@@ -49,11 +71,76 @@ extern crate i18nify_macro;
 pub use i18nify_macro::*;
 
 
+pub mod accept_language;
+#[cfg(feature = "actix")]
+pub mod actix;
 #[cfg(feature = "axum")]
 pub mod axum;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
 
+pub mod bidi;
+pub mod completeness;
+pub mod current;
+pub mod error;
+pub mod format;
+pub mod html;
+#[cfg(any(feature = "axum", feature = "actix"))]
+mod lang;
+pub mod rich;
 
+pub use completeness::Completeness;
+pub use current::{current_locale, with_locale, with_locale_sync};
+pub use error::LocalizedError;
+
+
+/// Maps the languages a client asks for to a locale.
+///
+/// With a `default` locale the derive implements it, matching the languages against the tags of
+/// the locale files such as `zh_CN.json` (`zh-CN`) and falling back to `default`:
+///
+/// ```
+/// mod demo {
+///     use i18nify::I18N;
+///     #[derive(I18N)]
+///     #[i18n(folder = "tests/zh_locales", default = "en")]
+///     pub struct DocLocale;
+/// }
+/// use demo::{DocLocale, Locale};
+/// use i18nify::Internationalization;
+///
+/// assert!(matches!(DocLocale.i("zh-cn"), Locale::ZhCn));
+/// assert!(matches!(DocLocale.negotiate(&["fr", "zh-Hans-CN", "zh-CN"], "en"), Locale::ZhCn));
+/// assert!(matches!(DocLocale.i("fr"), Locale::En));
+/// ```
 pub trait Internationalization {
     type Item;
     fn i(&self,lang:&str)->Self::Item;
-}
\ No newline at end of file
+
+    /// The language used when a request asks for none, `en` unless overridden.
+    fn default_language(&self) -> &str {
+        "en"
+    }
+
+    /// Picks the item for the languages a client prefers, most preferred first, such as the
+    /// ranges of an `Accept-Language` header.
+    ///
+    /// Uses the first language other than `*`, or `default_lang` if there is none. Override it
+    /// to match the preferences against the supported locales, e.g. with
+    /// [`accept_language::lookup`].
+    fn negotiate(&self, languages: &[&str], default_lang: &str) -> Self::Item {
+        let lang = languages
+            .iter()
+            .find(|lang| **lang != "*")
+            .copied()
+            .unwrap_or(default_lang);
+        self.i(lang)
+    }
+}
+/// A value identifying a locale by its BCP 47 language tag, implemented for the generated
+/// `Locale` enum.
+pub trait LanguageTag {
+    fn language_tag(&self) -> &str;
+}
@@ -0,0 +1,127 @@
+//! Rendering of translations containing markup tags.
+//!
+//! A translation such as `"Read the <link>terms</link> and <b>agree</b>"` keeps its tags in the
+//! plain `Locale::terms()` method. The derive additionally generates:
+//!
+//! - `Locale::terms_rich(renderer, ..)` which feeds the text and tags to a [`RichTextRenderer`],
+//!   so the same message can become HTML, ANSI escapes or UI framework nodes.
+//! - `Locale::terms_with(.., b_tag, link_tag)` which takes one closure per tag after the
+//!   placeholders, in alphabetical order, each receiving the already rendered inner text.
+//!
+//! Tag names are checked to be the same in every locale at compile time.
+//!
+//! ```
+//! mod demo {
+//!     use i18nify::I18N;
+//!     #[derive(I18N)]
+//!     #[i18n(folder = "tests/markup_locales")]
+//!     pub struct MarkupLocale;
+//! }
+//! use demo::{Locale, Name};
+//! use i18nify::rich::FnRenderer;
+//!
+//! assert_eq!(
+//!     Locale::En.terms(Name("Ada")),
+//!     "Read the <link>terms</link> and <b>agree</b>, Ada"
+//! );
+//!
+//! let html = Locale::De.terms_with(
+//!     Name("Ada"),
+//!     |inner| format!("<strong>{}</strong>", inner),
+//!     |inner| format!("<a href=\"/terms\">{}</a>", inner),
+//! );
+//! assert_eq!(
+//!     html,
+//!     "Lies die <a href=\"/terms\">Bedingungen</a> und <strong>stimme zu</strong>, Ada"
+//! );
+//!
+//! let ansi = Locale::En.terms_rich(
+//!     FnRenderer::new(|tag: &str, inner: String| match tag {
+//!         "b" => format!("\x1b[1m{}\x1b[0m", inner),
+//!         _ => format!("\x1b[4m{}\x1b[0m", inner),
+//!     }),
+//!     Name("Ada"),
+//! );
+//! assert_eq!(ansi, "Read the \x1b[4mterms\x1b[0m and \x1b[1magree\x1b[0m, Ada");
+//! ```
+
+/// Receives a translation piece by piece and builds the rendered output.
+///
+/// Tags are always properly nested, every [`start_tag`](RichTextRenderer::start_tag) is followed
+/// by a matching [`end_tag`](RichTextRenderer::end_tag).
+pub trait RichTextRenderer {
+    /// The rendered message.
+    type Output;
+
+    /// Text, with placeholders already interpolated.
+    fn text(&mut self, text: &str);
+
+    /// Start of the tag `name`, e.g. `link` for `<link>`.
+    fn start_tag(&mut self, name: &str);
+
+    /// End of the tag `name`.
+    fn end_tag(&mut self, name: &str);
+
+    /// Called once the whole translation has been rendered.
+    fn finish(self) -> Self::Output;
+}
+
+/// A [`RichTextRenderer`] calling a closure with each tag name and its rendered inner text.
+///
+/// ```
+/// use i18nify::rich::{FnRenderer, RichTextRenderer};
+///
+/// let mut renderer = FnRenderer::new(|tag: &str, inner: String| match tag {
+///     "b" => format!("<strong>{}</strong>", inner),
+///     _ => inner,
+/// });
+/// renderer.text("Please ");
+/// renderer.start_tag("b");
+/// renderer.text("agree");
+/// renderer.end_tag("b");
+///
+/// assert_eq!(renderer.finish(), "Please <strong>agree</strong>");
+/// ```
+pub struct FnRenderer<F> {
+    render_tag: F,
+    stack: Vec<String>,
+}
+
+impl<F> FnRenderer<F>
+where
+    F: FnMut(&str, String) -> String,
+{
+    pub fn new(render_tag: F) -> Self {
+        Self {
+            render_tag,
+            stack: vec![String::new()],
+        }
+    }
+}
+
+impl<F> RichTextRenderer for FnRenderer<F>
+where
+    F: FnMut(&str, String) -> String,
+{
+    type Output = String;
+
+    fn text(&mut self, text: &str) {
+        if let Some(current) = self.stack.last_mut() {
+            current.push_str(text);
+        }
+    }
+
+    fn start_tag(&mut self, _name: &str) {
+        self.stack.push(String::new());
+    }
+
+    fn end_tag(&mut self, name: &str) {
+        let inner = self.stack.pop().unwrap_or_default();
+        let rendered = (self.render_tag)(name, inner);
+        self.text(&rendered);
+    }
+
+    fn finish(mut self) -> Self::Output {
+        self.stack.swap_remove(0)
+    }
+}
@@ -0,0 +1,170 @@
+//! Locale resolution for [`tonic`](::tonic) gRPC servers.
+//!
+//! [`I18nifyInterceptor`] reads the languages from the request metadata, `accept-language` unless
+//! configured otherwise, and stores the negotiated locale in the request extensions. [`status`]
+//! builds a [`Status`] with a localized message.
+//!
+//! ```
+//! use i18nify::tonic::{status, I18nifyInterceptor};
+//! use tonic::{Code, Request, Status};
+//! # use i18nify::{Internationalization, LanguageTag};
+//! # #[derive(Clone, Copy)]
+//! # struct Locale(&'static str);
+//! # impl Locale {
+//! #     fn not_found(self) -> String { format!("not found ({})", self.0) }
+//! # }
+//! # impl LanguageTag for Locale {
+//! #     fn language_tag(&self) -> &str { self.0 }
+//! # }
+//! # #[derive(Clone)]
+//! # struct DocLocale;
+//! # impl Internationalization for DocLocale {
+//! #     type Item = Locale;
+//! #     fn i(&self, lang: &str) -> Locale { Locale(if lang == "de" { "de" } else { "en" }) }
+//! # }
+//!
+//! // `Server::builder().add_service(GreeterServer::with_interceptor(greeter, interceptor))`
+//! let interceptor = I18nifyInterceptor::new(DocLocale, "en").metadata_key("x-locale");
+//!
+//! fn handle(request: Request<()>) -> Result<(), Status> {
+//!     let locale = *request.extensions().get::<Locale>().unwrap();
+//!     Err(status(Code::NotFound, &locale, locale.not_found()))
+//! }
+//! ```
+//!
+//! Servers built from a `tower` stack can use [`I18nifyLayer`](crate::tower::I18nifyLayer) with
+//! the `tower` feature instead, the gRPC metadata being the HTTP headers.
+
+use tonic::{
+    metadata::{MetadataMap, MetadataValue},
+    service::Interceptor,
+    Code, Request, Status,
+};
+
+use crate::{accept_language, Internationalization, LanguageTag};
+
+/// Resolves the locale of a gRPC request from its metadata.
+///
+/// The values of the metadata key are read as `Accept-Language` ranges, so both
+/// `accept-language: de-CH, de;q=0.9` and `x-locale: de` work.
+#[derive(Clone)]
+pub struct I18nifyInterceptor<T> {
+    locale: T,
+    default_lang: String,
+    key: String,
+}
+
+impl<T: Internationalization> I18nifyInterceptor<T> {
+    /// Reads the `accept-language` metadata, falling back to `default_lang`.
+    pub fn new<S: AsRef<str>>(locale: T, default_lang: S) -> Self {
+        Self {
+            locale,
+            default_lang: default_lang.as_ref().to_string(),
+            key: "accept-language".to_string(),
+        }
+    }
+
+    /// Reads the languages from the metadata `key` instead, such as `x-locale`.
+    pub fn metadata_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into().to_ascii_lowercase();
+        self
+    }
+
+    /// The locale for the languages of `metadata`.
+    pub fn resolve(&self, metadata: &MetadataMap) -> T::Item {
+        let languages = metadata
+            .get_all(self.key.as_str())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(accept_language::parse)
+            .map(|range| range.tag)
+            .collect::<Vec<_>>();
+        let languages = languages.iter().map(String::as_str).collect::<Vec<_>>();
+
+        self.locale.negotiate(&languages, &self.default_lang)
+    }
+}
+
+impl<T> Interceptor for I18nifyInterceptor<T>
+where
+    T: Internationalization,
+    T::Item: Clone + Send + Sync + 'static,
+{
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let locale = self.resolve(request.metadata());
+        request.extensions_mut().insert(locale);
+        Ok(request)
+    }
+}
+
+/// A [`Status`] with a localized `message`, its language tag sent as `content-language` metadata.
+pub fn status<L: LanguageTag>(code: Code, locale: &L, message: impl Into<String>) -> Status {
+    let mut metadata = MetadataMap::new();
+    if let Ok(tag) = MetadataValue::try_from(locale.language_tag()) {
+        metadata.insert("content-language", tag);
+    }
+    Status::with_metadata(code, message, metadata)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Upper;
+
+    impl Internationalization for Upper {
+        type Item = String;
+
+        fn i(&self, lang: &str) -> String {
+            lang.to_uppercase()
+        }
+    }
+
+    struct Tag(&'static str);
+
+    impl LanguageTag for Tag {
+        fn language_tag(&self) -> &str {
+            self.0
+        }
+    }
+
+    fn intercept(
+        mut interceptor: I18nifyInterceptor<Upper>,
+        metadata: &[(&'static str, &str)],
+    ) -> String {
+        let mut request = Request::new(());
+        for (key, value) in metadata {
+            request
+                .metadata_mut()
+                .append(*key, MetadataValue::try_from(*value).unwrap());
+        }
+        let request = interceptor.call(request).unwrap();
+        request.extensions().get::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_interceptor_reads_accept_language() {
+        let interceptor = I18nifyInterceptor::new(Upper, "en");
+        assert_eq!(
+            intercept(interceptor.clone(), &[("accept-language", "da;q=0.5, de")]),
+            "DE"
+        );
+        assert_eq!(intercept(interceptor, &[("x-locale", "de")]), "EN");
+    }
+
+    #[test]
+    fn test_interceptor_reads_custom_key() {
+        let interceptor = I18nifyInterceptor::new(Upper, "en").metadata_key("X-Locale");
+        assert_eq!(intercept(interceptor, &[("x-locale", "fr")]), "FR");
+    }
+
+    #[test]
+    fn test_status() {
+        let status = status(Code::NotFound, &Tag("de"), "Nicht gefunden");
+
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Nicht gefunden");
+        assert_eq!(status.metadata().get("content-language").unwrap(), "de");
+    }
+}
@@ -0,0 +1,412 @@
+//! Locale resolution for any [`tower`](::tower) based stack, such as hyper or tonic servers,
+//! working on [`http::Request`] and [`http::Response`].
+//!
+//! [`I18nifyLayer`] resolves the locale of each request and stores it in the request extensions,
+//! where handlers read it back with `request.extensions().get::<Locale>()`.
+//!
+//! ```
+//! use http::{Request, Response};
+//! use i18nify::tower::I18nifyLayer;
+//! use tower::{service_fn, ServiceBuilder};
+//! # use i18nify::Internationalization;
+//! # #[derive(Clone)]
+//! # struct DocLocale;
+//! # impl Internationalization for DocLocale {
+//! #     type Item = String;
+//! #     fn i(&self, lang: &str) -> String { lang.to_string() }
+//! # }
+//!
+//! let service = ServiceBuilder::new()
+//!     .layer(I18nifyLayer::new(DocLocale, "en"))
+//!     .service(service_fn(|request: Request<()>| async move {
+//!         let locale = request.extensions().get::<String>().cloned().unwrap_or_default();
+//!         Ok::<_, std::convert::Infallible>(Response::new(locale))
+//!     }));
+//! ```
+
+use futures_util::future::BoxFuture;
+use http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, Request, Response};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+use crate::{Internationalization, LanguageTag};
+
+mod resolver;
+
+pub use resolver::{
+    from_fn, AcceptLanguage, Cookie, FnResolver, Header, LocaleResolver, PathPrefix, QueryParam,
+};
+
+pub struct I18nifyLayer<T: Internationalization + Clone> {
+    locale: T,
+    default_lang: String,
+    resolvers: Arc<[Box<dyn LocaleResolver>]>,
+    content_language: Option<fn(&T::Item) -> &str>,
+    vary: bool,
+}
+
+// A derived `Clone` would require `T::Item: Clone`
+impl<T: Internationalization + Clone> Clone for I18nifyLayer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            locale: self.locale.clone(),
+            default_lang: self.default_lang.clone(),
+            resolvers: self.resolvers.clone(),
+            content_language: self.content_language,
+            vary: self.vary,
+        }
+    }
+}
+
+impl<T: Internationalization + Clone> I18nifyLayer<T> {
+    /// Resolves the locale from the `Accept-Language` header, falling back to `default_lang`.
+    pub fn new<S: AsRef<str>>(locale: T, default_lang: S) -> Self {
+        Self::builder(locale, default_lang).build()
+    }
+
+    /// Negotiates the locale for the languages of all resolvers.
+    pub(crate) fn resolve(&self, parts: &Parts) -> T::Item {
+        let languages = self
+            .resolvers
+            .iter()
+            .flat_map(|resolver| resolver.resolve(parts))
+            .collect::<Vec<_>>();
+        let languages = languages.iter().map(String::as_str).collect::<Vec<_>>();
+
+        self.locale.negotiate(&languages, &self.default_lang)
+    }
+
+    /// The `Content-Language` of a response in `item`, if enabled.
+    pub(crate) fn content_language_of(&self, item: &T::Item) -> Option<HeaderValue> {
+        self.content_language
+            .and_then(|language_tag| HeaderValue::from_str(language_tag(item)).ok())
+    }
+
+    /// The request headers the resolvers read, `None` if any of them may read all headers.
+    #[cfg(feature = "actix")]
+    pub(crate) fn request_headers(&self) -> Option<Vec<HeaderName>> {
+        self.resolvers
+            .iter()
+            .try_fold(Vec::new(), |mut names, resolver| {
+                names.extend(resolver.headers()?);
+                Some(names)
+            })
+    }
+
+    /// The request headers to list in `Vary`, if enabled.
+    pub(crate) fn vary_headers(&self) -> Vec<HeaderName> {
+        if !self.vary {
+            return Vec::new();
+        }
+
+        self.resolvers
+            .iter()
+            .filter_map(|resolver| resolver.vary())
+            .collect()
+    }
+
+    /// Starts a layer resolving the locale from an ordered chain of sources.
+    ///
+    /// ```
+    /// use http::HeaderName;
+    /// use i18nify::tower::I18nifyLayer;
+    /// # use i18nify::Internationalization;
+    /// # #[derive(Clone)]
+    /// # struct DocLocale;
+    /// # impl Internationalization for DocLocale {
+    /// #     type Item = String;
+    /// #     fn i(&self, lang: &str) -> String { lang.to_string() }
+    /// # }
+    ///
+    /// // `?lang=de`, then the `lang` cookie, then `/de/...`, then `Accept-Language`
+    /// let layer = I18nifyLayer::builder(DocLocale, "en")
+    ///     .query("lang")
+    ///     .cookie("lang")
+    ///     .path_prefix()
+    ///     .accept_language()
+    ///     .build();
+    /// ```
+    pub fn builder<S: AsRef<str>>(locale: T, default_lang: S) -> I18nifyLayerBuilder<T> {
+        I18nifyLayerBuilder {
+            locale,
+            default_lang: default_lang.as_ref().to_string(),
+            resolvers: Vec::new(),
+            content_language: None,
+            vary: false,
+        }
+    }
+}
+
+/// Builds an [`I18nifyLayer`] with the sources of the locale in the order they are added.
+///
+/// Without any source the `Accept-Language` header is used.
+pub struct I18nifyLayerBuilder<T: Internationalization> {
+    locale: T,
+    default_lang: String,
+    resolvers: Vec<Box<dyn LocaleResolver>>,
+    content_language: Option<fn(&T::Item) -> &str>,
+    vary: bool,
+}
+
+impl<T: Internationalization + Clone> I18nifyLayerBuilder<T> {
+    /// Adds a custom source, e.g. one created with [`from_fn`].
+    pub fn resolver(mut self, resolver: impl LocaleResolver) -> Self {
+        self.resolvers.push(Box::new(resolver));
+        self
+    }
+
+    /// The ranges of the `Accept-Language` header.
+    pub fn accept_language(self) -> Self {
+        self.resolver(AcceptLanguage)
+    }
+
+    /// The query parameter `name`, e.g. `?lang=de`.
+    pub fn query(self, name: impl Into<String>) -> Self {
+        self.resolver(QueryParam::new(name))
+    }
+
+    /// The cookie `name`.
+    pub fn cookie(self, name: impl Into<String>) -> Self {
+        self.resolver(Cookie::new(name))
+    }
+
+    /// The first segment of the path, e.g. `/de/about`.
+    pub fn path_prefix(self) -> Self {
+        self.resolver(PathPrefix)
+    }
+
+    /// A header holding a single language tag.
+    pub fn header(self, name: HeaderName) -> Self {
+        self.resolver(Header::new(name))
+    }
+
+    /// Sets the `Content-Language` response header to the tag of the resolved locale, unless the
+    /// handler already set it.
+    pub fn content_language(mut self) -> Self
+    where
+        T::Item: LanguageTag,
+    {
+        self.content_language = Some(T::Item::language_tag);
+        self
+    }
+
+    /// Adds the request headers the resolvers read, such as `Accept-Language` or `Cookie`, to the
+    /// `Vary` response header, keeping the names the handler already listed.
+    pub fn vary(mut self) -> Self {
+        self.vary = true;
+        self
+    }
+
+    pub fn build(self) -> I18nifyLayer<T> {
+        let resolvers = if self.resolvers.is_empty() {
+            vec![Box::new(AcceptLanguage) as Box<dyn LocaleResolver>]
+        } else {
+            self.resolvers
+        };
+
+        I18nifyLayer {
+            locale: self.locale,
+            default_lang: self.default_lang,
+            resolvers: resolvers.into(),
+            content_language: self.content_language,
+            vary: self.vary,
+        }
+    }
+}
+
+impl<S, T> Layer<S> for I18nifyLayer<T>
+where
+    T: Internationalization + Clone,
+{
+    type Service = I18nifyService<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        I18nifyService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`I18nifyLayer`].
+#[derive(Clone)]
+pub struct I18nifyService<S, T: Internationalization + Clone> {
+    inner: S,
+    layer: I18nifyLayer<T>,
+}
+
+impl<S, T, ReqBody, ResBody> Service<Request<ReqBody>> for I18nifyService<S, T>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    T: Internationalization + Clone,
+    T::Item: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    // `BoxFuture` is a type alias for `Pin<Box<dyn Future + Send + 'a>>`
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let (mut parts, body) = request.into_parts();
+
+        let r = self.layer.resolve(&parts);
+        let content_language = self.layer.content_language_of(&r);
+        let vary = self.layer.vary_headers();
+
+        parts.extensions.insert(r.clone());
+
+        let future = crate::with_locale(r, self.inner.call(Request::from_parts(parts, body)));
+        Box::pin(async move {
+            let mut response = future.await?;
+            let headers = response.headers_mut();
+            if let Some(content_language) = content_language {
+                headers
+                    .entry(header::CONTENT_LANGUAGE)
+                    .or_insert(content_language);
+            }
+            for name in vary {
+                append_vary(headers, name);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Adds `name` to the `Vary` header unless it is listed already or the header is `*`.
+fn append_vary(headers: &mut HeaderMap, name: HeaderName) {
+    let values = headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+
+    if !vary_lists(values, name.as_str()) {
+        headers.append(header::VARY, HeaderValue::from(name));
+    }
+}
+
+/// Whether the `Vary` header `values` already cover the header `name`.
+pub(crate) fn vary_lists<'a>(values: impl Iterator<Item = &'a str>, name: &str) -> bool {
+    values
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|listed| listed == "*" || listed.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    #[derive(Clone)]
+    struct Tag;
+
+    #[derive(Clone)]
+    struct Resolved(String);
+
+    impl LanguageTag for Resolved {
+        fn language_tag(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl Internationalization for Tag {
+        type Item = Resolved;
+
+        fn i(&self, lang: &str) -> Resolved {
+            Resolved(lang.to_string())
+        }
+    }
+
+    async fn respond(layer: I18nifyLayer<Tag>, handler_vary: Option<&'static str>) -> Response<()> {
+        let service = layer.layer(service_fn(move |_: Request<()>| async move {
+            let mut response = Response::new(());
+            if let Some(vary) = handler_vary {
+                response
+                    .headers_mut()
+                    .insert(header::VARY, HeaderValue::from_static(vary));
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static("fr"));
+            }
+            Ok::<_, Infallible>(response)
+        }));
+
+        let request = Request::builder()
+            .header("accept-language", "de")
+            .header("cookie", "lang=da")
+            .body(())
+            .unwrap();
+        service.oneshot(request).await.unwrap()
+    }
+
+    fn vary(response: &Response<()>) -> Vec<&str> {
+        response
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_headers_are_opt_in() {
+        let response = respond(I18nifyLayer::new(Tag, "en"), None).await;
+        assert!(response.headers().get(header::CONTENT_LANGUAGE).is_none());
+        assert!(vary(&response).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sets_content_language_and_vary() {
+        let layer = I18nifyLayer::builder(Tag, "en")
+            .cookie("lang")
+            .accept_language()
+            .content_language()
+            .vary()
+            .build();
+        let response = respond(layer, None).await;
+
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "da");
+        assert_eq!(vary(&response), ["cookie", "accept-language"]);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_handler_headers() {
+        let layer = I18nifyLayer::builder(Tag, "en")
+            .cookie("lang")
+            .accept_language()
+            .content_language()
+            .vary()
+            .build();
+        let response = respond(layer, Some("Accept-Encoding, Accept-Language")).await;
+
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "fr");
+        assert_eq!(
+            vary(&response),
+            ["Accept-Encoding, Accept-Language", "cookie"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sets_current_locale() {
+        let service = I18nifyLayer::new(Tag, "en").layer(service_fn(|_: Request<()>| async {
+            let locale = crate::current_locale::<Resolved>().map(|locale| locale.0);
+            Ok::<_, Infallible>(Response::new(locale))
+        }));
+
+        let request = Request::builder()
+            .header("accept-language", "de")
+            .body(())
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.into_body().as_deref(), Some("de"));
+    }
+}
@@ -0,0 +1,264 @@
+use http::{header, request::Parts, HeaderName};
+
+use crate::accept_language;
+
+/// A source of the languages a request asks for, such as a header, a cookie or the profile of the
+/// signed in user.
+///
+/// [`I18nifyLayer`](super::I18nifyLayer) asks its resolvers in order and hands the languages of
+/// all of them, in that order, to [`Internationalization::negotiate`](crate::Internationalization::negotiate).
+pub trait LocaleResolver: Send + Sync + 'static {
+    /// The requested languages, most preferred first, empty when this source has none.
+    fn resolve(&self, parts: &Parts) -> Vec<String>;
+
+    /// The request header the resolved languages depend on, for the `Vary` response header.
+    fn vary(&self) -> Option<HeaderName> {
+        None
+    }
+
+    /// The request headers `resolve` reads, `None` when it may read any of them.
+    ///
+    /// Frameworks with their own request types, such as actix-web, copy only these headers into
+    /// the [`Parts`] handed to `resolve`.
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        None
+    }
+}
+
+/// The ranges of the `Accept-Language` header, ordered by their quality.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptLanguage;
+
+impl LocaleResolver for AcceptLanguage {
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        parts
+            .headers
+            .get_all(header::ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(accept_language::parse)
+            .map(|range| range.tag)
+            .collect()
+    }
+
+    fn vary(&self) -> Option<HeaderName> {
+        Some(header::ACCEPT_LANGUAGE)
+    }
+
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        self.vary().map(|name| vec![name])
+    }
+}
+
+/// A query parameter such as `?lang=de`.
+#[derive(Debug, Clone)]
+pub struct QueryParam(String);
+
+impl QueryParam {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl LocaleResolver for QueryParam {
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .filter(|(name, _)| *name == self.0)
+            .map(|(_, value)| value)
+            .filter(|value| accept_language::is_language_tag(value))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        Some(Vec::new())
+    }
+}
+
+/// A cookie such as `lang=de`.
+#[derive(Debug, Clone)]
+pub struct Cookie(String);
+
+impl Cookie {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl LocaleResolver for Cookie {
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .filter(|(name, _)| *name == self.0)
+            .map(|(_, value)| value.trim_matches('"'))
+            .filter(|value| accept_language::is_language_tag(value))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn vary(&self) -> Option<HeaderName> {
+        Some(header::COOKIE)
+    }
+
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        self.vary().map(|name| vec![name])
+    }
+}
+
+/// The first segment of the path, `de` for `/de/about`.
+///
+/// Only segments shaped like a language tag are used, the path itself is left as is. A segment
+/// such as `api` looks like one too, so pair this with a `negotiate` that checks the supported
+/// locales.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PathPrefix;
+
+impl LocaleResolver for PathPrefix {
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        parts
+            .uri
+            .path()
+            .trim_start_matches('/')
+            .split('/')
+            .next()
+            .filter(|segment| accept_language::is_language_tag(segment))
+            .map(|segment| vec![segment.to_string()])
+            .unwrap_or_default()
+    }
+
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        Some(Vec::new())
+    }
+}
+
+/// A header holding a single language tag, such as `X-Language: de`.
+#[derive(Debug, Clone)]
+pub struct Header(HeaderName);
+
+impl Header {
+    pub fn new(name: HeaderName) -> Self {
+        Self(name)
+    }
+}
+
+impl LocaleResolver for Header {
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        parts
+            .headers
+            .get(&self.0)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| accept_language::is_language_tag(value))
+            .map(|value| vec![value.to_string()])
+            .unwrap_or_default()
+    }
+
+    fn vary(&self) -> Option<HeaderName> {
+        Some(self.0.clone())
+    }
+
+    fn headers(&self) -> Option<Vec<HeaderName>> {
+        self.vary().map(|name| vec![name])
+    }
+}
+
+/// A resolver calling a closure, created with [`from_fn`].
+#[derive(Debug, Clone)]
+pub struct FnResolver<F>(F);
+
+/// Resolves the language with a closure, e.g. from the user stored in the request extensions
+/// by an authentication layer.
+///
+/// ```
+/// use http::request::Parts;
+/// use i18nify::tower::from_fn;
+///
+/// #[derive(Clone)]
+/// struct User {
+///     language: String,
+/// }
+///
+/// let resolver = from_fn(|parts: &Parts| {
+///     parts.extensions.get::<User>().map(|user| user.language.clone())
+/// });
+/// ```
+pub fn from_fn<F>(f: F) -> FnResolver<F>
+where
+    F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+{
+    FnResolver(f)
+}
+
+impl<F> LocaleResolver for FnResolver<F>
+where
+    F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+{
+    fn resolve(&self, parts: &Parts) -> Vec<String> {
+        (self.0)(parts).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::Request;
+
+    fn parts(uri: &str, headers: &[(&str, &str)]) -> Parts {
+        let mut request = Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn test_accept_language() {
+        let parts = parts("/", &[("accept-language", "en;q=0.5, de")]);
+        assert_eq!(AcceptLanguage.resolve(&parts), ["de", "en"]);
+    }
+
+    #[test]
+    fn test_query_param() {
+        let resolver = QueryParam::new("lang");
+        assert_eq!(resolver.resolve(&parts("/?page=2&lang=da", &[])), ["da"]);
+        assert!(resolver
+            .resolve(&parts("/?lang=%3Cscript%3E", &[]))
+            .is_empty());
+        assert!(resolver.resolve(&parts("/", &[])).is_empty());
+    }
+
+    #[test]
+    fn test_cookie() {
+        let parts = parts("/", &[("cookie", "session=abc; lang=zh-CN")]);
+        assert_eq!(Cookie::new("lang").resolve(&parts), ["zh-CN"]);
+        assert!(Cookie::new("locale").resolve(&parts).is_empty());
+    }
+
+    #[test]
+    fn test_path_prefix() {
+        assert_eq!(PathPrefix.resolve(&parts("/de/about", &[])), ["de"]);
+        assert_eq!(PathPrefix.resolve(&parts("/en-GB", &[])), ["en-GB"]);
+        assert!(PathPrefix.resolve(&parts("/2024/posts", &[])).is_empty());
+        assert!(PathPrefix.resolve(&parts("/", &[])).is_empty());
+    }
+
+    #[test]
+    fn test_header_and_closure() {
+        let parts = parts("/", &[("x-language", "fr")]);
+        let header = Header::new(HeaderName::from_static("x-language"));
+        assert_eq!(header.resolve(&parts), ["fr"]);
+
+        let closure = from_fn(|_: &Parts| Some("nl".to_string()));
+        assert_eq!(closure.resolve(&parts), ["nl"]);
+    }
+}